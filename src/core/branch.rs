@@ -0,0 +1,243 @@
+//! Branch naming templates (`branch_template` / `branch_templates` in config).
+//!
+//! Templates use `{placeholder}` syntax; see [`PLACEHOLDERS`]. Separators left dangling by an
+//! empty placeholder (e.g. `{ticket}-{slug}` without a description) are tidied away; substituted
+//! values other than the slug are kept verbatim.
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+
+/// Placeholders accepted in branch templates.
+pub const PLACEHOLDERS: &[&str] = &["prefix", "ticket", "slug", "user", "date", "type"];
+
+/// Characters treated as separators when tidying the joins between template parts.
+const SEPARATORS: [char; 4] = ['/', '-', '_', '.'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        match rest.find(['{', '}']) {
+            None => {
+                segments.push(Segment::Literal(rest));
+                break;
+            }
+            Some(idx) if rest.as_bytes()[idx] == b'}' => {
+                bail!("Unmatched '}}' in branch template '{}'", template)
+            }
+            Some(idx) => {
+                if idx > 0 {
+                    segments.push(Segment::Literal(&rest[..idx]));
+                }
+                let after = &rest[idx + 1..];
+                let Some(end) = after.find('}') else {
+                    bail!("Unclosed '{{' in branch template '{}'", template);
+                };
+                let name = &after[..end];
+                if !PLACEHOLDERS.contains(&name) {
+                    bail!(
+                        "Unknown placeholder '{{{}}}' in branch template '{}' (expected one of: {})",
+                        name,
+                        template,
+                        PLACEHOLDERS
+                            .iter()
+                            .map(|p| format!("{{{}}}", p))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+                segments.push(Segment::Placeholder(name));
+                rest = &after[end + 1..];
+            }
+        }
+    }
+    Ok(segments)
+}
+
+/// Check that `template` parses, includes `{ticket}`, and renders to a valid git branch name.
+pub fn validate(template: &str) -> Result<()> {
+    let segments = parse(template)?;
+    if !segments.contains(&Segment::Placeholder("ticket")) {
+        bail!(
+            "Branch template '{}' must contain {{ticket}} so branch names stay unique per ticket",
+            template
+        );
+    }
+
+    let sample: HashMap<&str, String> = [
+        ("prefix", "feature"),
+        ("ticket", "ABC-123"),
+        ("slug", "short-summary"),
+        ("user", "jdoe"),
+        ("date", "20240101"),
+        ("type", "bugfix"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k, v.to_string()))
+    .collect();
+    let rendered = render(template, &sample)?;
+    if !git2::Reference::is_valid_name(&format!("refs/heads/{}", rendered)) {
+        bail!(
+            "Branch template '{}' does not produce a valid git branch name (e.g. '{}')",
+            template,
+            rendered
+        );
+    }
+    Ok(())
+}
+
+/// Render `template` with `values`; missing values render as empty and their separators are dropped.
+///
+/// Only the literal text joining the parts of the template (and the slug) is tidied, so ids such
+/// as `ABC--1` come through unchanged.
+pub fn render(template: &str, values: &HashMap<&str, String>) -> Result<String> {
+    let mut out = String::new();
+    // Literal text since the last non-empty placeholder.
+    let mut join = String::new();
+    for segment in parse(template)? {
+        match segment {
+            Segment::Literal(text) => join.push_str(text),
+            Segment::Placeholder(name) => {
+                let value = match values.get(name) {
+                    Some(slug) if name == "slug" => tidy(slug),
+                    Some(value) => value.clone(),
+                    None => continue,
+                };
+                if value.is_empty() {
+                    continue;
+                }
+                let joined = collapse_separators(&join);
+                out.push_str(if out.is_empty() {
+                    joined.trim_start_matches(SEPARATORS)
+                } else {
+                    &joined
+                });
+                out.push_str(&value);
+                join.clear();
+            }
+        }
+    }
+    let trailing = collapse_separators(&join);
+    let trailing = trailing.trim_end_matches(SEPARATORS);
+    out.push_str(if out.is_empty() {
+        trailing.trim_start_matches(SEPARATORS)
+    } else {
+        trailing
+    });
+    Ok(out)
+}
+
+/// Collapse runs of separators, e.g. those left behind by empty placeholders.
+fn collapse_separators(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        let last = out.chars().last();
+        let is_sep = SEPARATORS.contains(&c);
+        match last {
+            // A slash wins over a dangling `-`/`_`/`.` before it.
+            Some('-' | '_' | '.') if c == '/' => {
+                out.pop();
+                out.push('/');
+            }
+            Some('/') if is_sep => continue,
+            Some(prev) if prev == c && is_sep => continue,
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Collapse separators and trim them from both ends.
+fn tidy(name: &str) -> String {
+    collapse_separators(name)
+        .trim_matches(SEPARATORS)
+        .to_string()
+}
+
+/// Truncate `slug` to at most `max` characters without leaving a trailing hyphen.
+pub fn truncate_slug(slug: &str, max: Option<usize>) -> String {
+    let Some(max) = max else {
+        return slug.to_string();
+    };
+    let truncated: String = slug.chars().take(max).collect();
+    truncated.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[test]
+    fn render_substitutes_placeholders() {
+        let name = render(
+            "users/{user}/{ticket}/{slug}",
+            &values(&[("user", "jdoe"), ("ticket", "ABC-1"), ("slug", "fix-it")]),
+        )
+        .unwrap();
+        assert_eq!(name, "users/jdoe/ABC-1/fix-it");
+    }
+
+    #[test]
+    fn render_drops_separators_of_empty_placeholders() {
+        let name = render(
+            "{prefix}/{ticket}-{slug}",
+            &values(&[("prefix", "feature"), ("ticket", "ABC-1")]),
+        )
+        .unwrap();
+        assert_eq!(name, "feature/ABC-1");
+
+        let name = render(
+            "{type}/{user}/{ticket}",
+            &values(&[("type", "bugfix"), ("ticket", "ABC-1")]),
+        )
+        .unwrap();
+        assert_eq!(name, "bugfix/ABC-1");
+
+        let name = render(
+            "wip/{user}/{ticket}-{slug}",
+            &values(&[("ticket", "ABC-1")]),
+        )
+        .unwrap();
+        assert_eq!(name, "wip/ABC-1");
+    }
+
+    #[test]
+    fn render_keeps_substituted_ids_verbatim() {
+        let name = render(
+            "{prefix}/{ticket}-{slug}",
+            &values(&[
+                ("prefix", "feature"),
+                ("ticket", "ABC--1"),
+                ("slug", "fix--it-"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(name, "feature/ABC--1-fix-it");
+    }
+
+    #[test]
+    fn validate_rejects_bad_templates() {
+        assert!(validate("{prefix}/{ticket}-{slug}").is_ok());
+        assert!(validate("{prefix}/{slug}").is_err());
+        assert!(validate("{prefix}/{ticket}/{owner}").is_err());
+        assert!(validate("{prefix}/{ticket").is_err());
+        assert!(validate("{prefix}}/{ticket}").is_err());
+        assert!(validate("bad name/{ticket}").is_err());
+    }
+
+    #[test]
+    fn truncate_slug_respects_limit() {
+        assert_eq!(truncate_slug("add-new-feature", Some(8)), "add-new");
+        assert_eq!(truncate_slug("add-new-feature", None), "add-new-feature");
+    }
+}
@@ -0,0 +1,186 @@
+//! Carry untracked local files (`carry_files`) from a repo's main checkout into ticket worktrees.
+//!
+//! Only files git reports as untracked or ignored are carried; tracked files already come with
+//! the worktree. Symlinked files are always unlinked, never followed, when a worktree goes away.
+
+use crate::core::config::{CarryMode, RepoDefinition};
+use crate::core::git;
+use crate::core::ticket::CarriedFiles;
+use anyhow::{Context, Result, bail};
+use log::{debug, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Carry the files matching `patterns` from `source` into `worktree` using `mode`.
+/// Existing files (or links) at the destination are replaced.
+pub fn carry(
+    source: &Path,
+    worktree: &Path,
+    patterns: &[String],
+    mode: CarryMode,
+) -> Result<CarriedFiles> {
+    let mut carried = CarriedFiles {
+        patterns: patterns.to_vec(),
+        mode,
+        files: Vec::new(),
+    };
+    if patterns.is_empty() {
+        return Ok(carried);
+    }
+
+    let files = git::untracked_files_matching(source, patterns)
+        .with_context(|| format!("Failed to list files to carry from {:?}", source))?;
+    for relative in files {
+        let from = source.join(&relative);
+        let to = worktree.join(&relative);
+        place(&from, &to, mode).with_context(|| format!("Failed to carry {:?}", relative))?;
+        debug!("Carried {:?} into {:?} ({:?})", relative, worktree, mode);
+        carried.files.push(relative);
+    }
+    Ok(carried)
+}
+
+/// Carry the repo's configured `carry_files` into a freshly created worktree.
+///
+/// Failures are logged rather than returned: the worktree is usable without the files and
+/// `tix refresh-files` can retry. Returns `None` when nothing is configured or carrying failed.
+pub fn carry_into_new_worktree(
+    alias: &str,
+    repo_def: &RepoDefinition,
+    worktree: &Path,
+) -> Option<CarriedFiles> {
+    if repo_def.carry_files.is_empty() {
+        return None;
+    }
+    match carry(
+        &repo_def.path,
+        worktree,
+        &repo_def.carry_files,
+        repo_def.carry_mode,
+    ) {
+        Ok(carried) => {
+            info!(
+                "[{}] Carried {} local file(s) into the worktree",
+                alias,
+                carried.files.len()
+            );
+            Some(carried)
+        }
+        Err(e) => {
+            warn!("[{}] {:#}", alias, e);
+            info!("Hint: Run 'tix refresh-files' to retry.");
+            None
+        }
+    }
+}
+
+fn place(from: &Path, to: &Path, mode: CarryMode) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if let Ok(existing) = fs::symlink_metadata(to) {
+        if existing.is_dir() {
+            bail!("{:?} is a directory in the worktree; not replacing it", to);
+        }
+        // Removes the link itself when `to` is a symlink.
+        fs::remove_file(to)?;
+    }
+    match mode {
+        CarryMode::Copy => {
+            fs::copy(from, to)?;
+        }
+        CarryMode::Symlink => symlink_file(from, to)?,
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(from, to)
+}
+
+#[cfg(windows)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(from, to)
+}
+
+/// Unlink carried files that are symlinks so deleting `worktree` can never follow them.
+pub fn unlink_symlinks(worktree: &Path, files: &[PathBuf]) -> Result<()> {
+    for relative in files {
+        let path = worktree.join(relative);
+        if let Ok(meta) = fs::symlink_metadata(&path)
+            && meta.file_type().is_symlink()
+        {
+            fs::remove_file(&path).with_context(|| format!("Failed to unlink {:?}", path))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    fn checkout_with_local_files() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), ".env\ncerts/\n").unwrap();
+        fs::write(dir.path().join(".env"), "SECRET=1").unwrap();
+        fs::create_dir_all(dir.path().join("certs")).unwrap();
+        fs::write(dir.path().join("certs/local.pem"), "pem").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not carried").unwrap();
+        dir
+    }
+
+    #[test]
+    fn copies_matching_untracked_files() {
+        let source = checkout_with_local_files();
+        let worktree = TempDir::new().unwrap();
+        let patterns = vec![".env".to_string(), "certs/*.pem".to_string()];
+
+        let carried = carry(source.path(), worktree.path(), &patterns, CarryMode::Copy).unwrap();
+        assert_eq!(
+            carried.files,
+            vec![PathBuf::from(".env"), PathBuf::from("certs/local.pem")]
+        );
+        assert_eq!(
+            fs::read_to_string(worktree.path().join(".env")).unwrap(),
+            "SECRET=1"
+        );
+        assert!(!worktree.path().join("notes.txt").exists());
+
+        // Carrying again replaces stale copies.
+        fs::write(source.path().join(".env"), "SECRET=2").unwrap();
+        carry(source.path(), worktree.path(), &patterns, CarryMode::Copy).unwrap();
+        assert_eq!(
+            fs::read_to_string(worktree.path().join(".env")).unwrap(),
+            "SECRET=2"
+        );
+    }
+
+    #[test]
+    fn symlinks_are_unlinked_without_touching_targets() {
+        let source = checkout_with_local_files();
+        let worktree = TempDir::new().unwrap();
+        let carried = carry(
+            source.path(),
+            worktree.path(),
+            &[".env".to_string()],
+            CarryMode::Symlink,
+        )
+        .unwrap();
+        let link = worktree.path().join(".env");
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+
+        unlink_symlinks(worktree.path(), &carried.files).unwrap();
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert!(source.path().join(".env").exists());
+    }
+}
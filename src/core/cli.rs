@@ -1,5 +1,8 @@
 //! Command-line interface definitions for tix.
 
+use crate::core::output::OutputFormat;
+use crate::core::ticket::TicketStatus;
+use chrono::TimeDelta;
 use clap::builder::Styles;
 use clap::builder::styling::AnsiColor;
 use clap::{Parser, Subcommand};
@@ -19,6 +22,15 @@ pub struct Cli {
     #[command(flatten)]
     pub verbose: Verbosity<InfoLevel>,
 
+    /// Output format for read commands (list, info, status, pr status)
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Print what a mutating command (setup, add, remove, destroy, setup-repos, add-repo, gc)
+    /// would change without changing anything
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -26,10 +38,11 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 /// Supported subcommands for tix.
 pub enum Commands {
-    /// Add a repository worktree to an existing ticket
+    /// Add repository worktrees to an existing ticket
     Add {
-        /// Repository alias
-        repo: String,
+        /// Repository aliases or @group names
+        #[arg(required = true)]
+        repos: Vec<String>,
 
         /// Ticket name. If omitted, tries to infer from current directory
         #[arg(short, long)]
@@ -40,6 +53,16 @@ pub enum Commands {
         branch: Option<String>,
     },
 
+    /// Archive a ticket: remove its worktrees but keep branches and metadata
+    Archive {
+        /// Ticket name
+        ticket: String,
+
+        /// Archive even if worktrees have uncommitted changes (they are lost)
+        #[arg(short, long)]
+        force: bool,
+    },
+
     /// Register a repository in the configuration
     AddRepo {
         // Repository reference
@@ -72,19 +95,86 @@ pub enum Commands {
         /// Skip confirmation prompts
         #[arg(short, long)]
         force: bool,
+
+        /// Skip only the unpushed/unmerged commit check
+        #[arg(long)]
+        allow_unpushed: bool,
+
+        /// Delete the ticket's local branches that are merged (all of them with --force)
+        #[arg(long)]
+        delete_branches: bool,
+
+        /// Delete the ticket's branches on the remote that are merged (all of them with --force)
+        #[arg(long)]
+        delete_remote_branches: bool,
+    },
+
+    /// Run a command in every worktree of a ticket
+    Exec {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+
+        /// Only run in these repo aliases (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        repos: Vec<String>,
+
+        /// Run in all worktrees concurrently (bounded by max_parallel_jobs)
+        #[arg(short, long)]
+        parallel: bool,
+
+        /// Command to run; a single argument is passed to the shell
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Destroy tickets whose branches are merged or deleted on the remote
+    Gc {
+        /// Only consider tickets created at least this long ago (e.g., 30d, 2w, 12h)
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        older_than: Option<TimeDelta>,
+
+        /// Destroy every qualifying ticket without asking
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Initialize tix configuration interactively
     Init,
 
-    /// Remove a repository worktree from a ticket
+    /// Remove repository worktrees from a ticket
     Remove {
-        /// Repository alias to remove
-        repo: String,
+        /// Repository aliases or @group names to remove
+        #[arg(required = true)]
+        repos: Vec<String>,
 
         /// Ticket name. If omitted, inferred from context
         #[arg(short, long)]
         ticket: Option<String>,
+
+        /// Remove even if the branch has commits that are not pushed or merged
+        #[arg(long)]
+        allow_unpushed: bool,
+    },
+
+    /// Copy or symlink `carry_files` into the ticket's worktrees again
+    RefreshFiles {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+
+    /// Re-link ticket worktrees after the tickets or code directory moved
+    Repair {
+        /// Ticket name. If omitted, every ticket is repaired
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+
+    /// Restore an archived ticket and recreate its worktrees
+    Restore {
+        /// Ticket name
+        ticket: String,
     },
 
     /// Create a new ticket workspace with repository worktrees
@@ -100,16 +190,44 @@ pub enum Commands {
         #[arg(short, long)]
         all: bool,
 
-        /// Specific repo aliases to include
+        /// Specific repo aliases or @group names to include
         #[arg(num_args(0..))]
         repos: Vec<String>,
+
+        /// Keep completed worktrees and branches if a later repo fails (skip rollback)
+        #[arg(long)]
+        keep_partial: bool,
+
+        /// Maximum number of repositories to process in parallel
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Ticket type (e.g., bugfix); selects a `branch_templates` entry and fills `{type}`
+        #[arg(long = "type", value_name = "TYPE")]
+        ticket_type: Option<String>,
+
+        /// Apply a `[templates.<name>]` layout (repos, branch prefix, base branches, commands)
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+
+        /// Fill the description, branch prefix and issue details from the issue tracker
+        #[arg(long, visible_alias = "from-jira")]
+        from_tracker: bool,
     },
 
     /// Clone all registered repositories
-    SetupRepos,
+    SetupRepos {
+        /// Maximum number of repositories to clone in parallel
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
 
-    /// Validate configuration and environment
-    Doctor,
+    /// Validate configuration, environment and ticket workspaces
+    Doctor {
+        /// Repair safe problems (stale worktree metadata, missing or unlinked worktrees)
+        #[arg(long)]
+        fix: bool,
+    },
 
     // Req 1: Support shell completions
     /// Generate shell completions
@@ -126,7 +244,74 @@ pub enum Commands {
     },
 
     /// List all ticket workspaces
-    List,
+    List {
+        /// Show archived tickets instead of active ones
+        #[arg(long)]
+        archived: bool,
+
+        /// Only show tickets with this lifecycle status
+        #[arg(long, value_enum)]
+        status: Option<TicketStatus>,
+    },
+
+    /// Show branch and working-tree state for every repo in a ticket
+    Status {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+
+        #[command(subcommand)]
+        command: Option<StatusCommands>,
+    },
+
+    /// Push every ticket branch and set its upstream
+    Push {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+
+        /// Overwrite diverged remote branches if they still match the last fetch
+        #[arg(long)]
+        force_with_lease: bool,
+    },
+
+    /// Fetch and update every ticket branch onto its base branch
+    Sync {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+
+        /// Rebase ticket branches onto the base
+        #[arg(long, conflicts_with = "merge")]
+        rebase: bool,
+
+        /// Merge the base into ticket branches (default)
+        #[arg(long)]
+        merge: bool,
+
+        /// Abort an interrupted sync and discard its saved state
+        #[arg(long, conflicts_with_all = ["rebase", "merge"])]
+        abort: bool,
+    },
+
+    /// Manage named groups of repositories (used as @group)
+    Groups {
+        #[command(subcommand)]
+        command: GroupCommands,
+    },
+
+    /// Work with the ticket's issue in the issue tracker
+    #[command(visible_alias = "jira")]
+    Issue {
+        #[command(subcommand)]
+        command: IssueCommands,
+    },
+
+    /// Open and track GitHub pull requests for the ticket's branches
+    Pr {
+        #[command(subcommand)]
+        command: PrCommands,
+    },
 
     /// Manage registered plugins
     Plugins {
@@ -139,6 +324,107 @@ pub enum Commands {
     Plugin(Vec<String>),
 }
 
+impl Commands {
+    /// Whether the command honours the global `--format` option.
+    pub fn supports_format(&self) -> bool {
+        matches!(
+            self,
+            Commands::List { .. }
+                | Commands::Info { .. }
+                | Commands::Status { command: None, .. }
+                | Commands::Pr {
+                    command: PrCommands::Status { .. }
+                }
+        )
+    }
+
+    /// Whether the command honours the global `--dry-run` flag.
+    pub fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            Commands::Setup { .. }
+                | Commands::Add { .. }
+                | Commands::Remove { .. }
+                | Commands::Destroy { .. }
+                | Commands::SetupRepos { .. }
+                | Commands::AddRepo { .. }
+                | Commands::Gc { .. }
+        )
+    }
+}
+
+#[derive(Subcommand, Debug)]
+/// Issue tracker subcommands.
+pub enum IssueCommands {
+    /// Re-fetch summary, issue type, assignee and status into the ticket metadata
+    Refresh {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+/// Pull request subcommands.
+pub enum PrCommands {
+    /// Open one pull request per repo branch and cross-link them
+    Create {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+        /// Open the pull requests as drafts
+        #[arg(long)]
+        draft: bool,
+    },
+    /// Show review and CI state of the ticket's pull requests
+    Status {
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+/// Ticket lifecycle subcommands.
+pub enum StatusCommands {
+    /// Change the ticket's lifecycle status
+    Set {
+        /// New status
+        #[arg(value_enum)]
+        status: TicketStatus,
+
+        /// Ticket name. If omitted, inferred from context
+        #[arg(short, long)]
+        ticket: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+/// Repository group subcommands.
+pub enum GroupCommands {
+    /// List groups and their repositories
+    List,
+
+    /// Create a group or add repositories to it
+    Add {
+        /// Group name
+        name: String,
+
+        /// Repository aliases to add
+        #[arg(required = true)]
+        repos: Vec<String>,
+    },
+
+    /// Remove repositories from a group, or the whole group if none are given
+    Remove {
+        /// Group name
+        name: String,
+
+        /// Repository aliases to remove
+        repos: Vec<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 /// Plugin management subcommands.
 pub enum PluginCommands {
@@ -174,3 +460,35 @@ pub enum PluginCommands {
         name: Option<String>,
     },
 }
+
+/// Parse an age such as `30d`, `2w` or `12h` (`--older-than`).
+fn parse_age(value: &str) -> Result<TimeDelta, String> {
+    let invalid = || format!("invalid age '{}': expected e.g. 30d, 2w or 12h", value);
+    let Some((split, unit)) = value.char_indices().last() else {
+        return Err(invalid());
+    };
+    let amount: u32 = value[..split].parse().map_err(|_| invalid())?;
+    let amount = i64::from(amount);
+    match unit {
+        'h' => TimeDelta::try_hours(amount),
+        'd' => TimeDelta::try_days(amount),
+        'w' => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_age_accepts_hours_days_and_weeks() {
+        assert_eq!(parse_age("12h"), Ok(TimeDelta::hours(12)));
+        assert_eq!(parse_age("30d"), Ok(TimeDelta::days(30)));
+        assert_eq!(parse_age("2w"), Ok(TimeDelta::weeks(2)));
+        for bad in ["", "d", "30", "30m", "-1d", "1.5d", "3é"] {
+            assert!(parse_age(bad).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+}
@@ -1,20 +1,86 @@
 //! Add a repo worktree to an existing ticket.
 
-use crate::core::commands::common::{build_branch_name, locate_ticket_root};
-use crate::core::config::Config;
+use crate::core::carry;
+use crate::core::commands::common::{
+    branch_name_for_metadata, combine_failures, locate_ticket_root,
+};
+use crate::core::config::{Config, RepoDefinition};
 use crate::core::git;
-use crate::core::ticket::Ticket;
+use crate::core::hooks::{self, HookEvent, HookTarget};
+use crate::core::plan::{self, Action};
+use crate::core::ticket::{Ticket, metadata_path};
 use anyhow::{Context, Result, anyhow, bail};
-use log::{info, warn};
-use std::path::Path;
+use log::{error, info, warn};
+use std::path::{Path, PathBuf};
 
-/// Run the add command.
-pub fn run(repo_alias: &str, ticket: Option<&str>, branch: Option<&str>) -> Result<()> {
+/// Run the add command. `repos` may contain `@group` references. With `dry_run`, only print
+/// the plan.
+pub fn run(
+    repos: &[String],
+    ticket: Option<&str>,
+    branch: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
     let config = Config::load()?;
     let ticket_root = locate_ticket_root(ticket, &config)?;
     ensure_ticket_exists(&ticket_root)?;
 
-    let ticket_meta = Ticket::load(&ticket_root).context(
+    let aliases = config.expand_aliases(repos)?;
+    if dry_run {
+        let mut actions = Vec::new();
+        for alias in &aliases {
+            let plan = plan_add(&config, &ticket_root, alias, branch)
+                .with_context(|| format!("Failed to add '{}'", alias))?;
+            actions.extend(describe(&config, &ticket_root, &plan)?);
+        }
+        plan::print(&actions);
+        return Ok(());
+    }
+    if let [alias] = aliases.as_slice() {
+        return add_repo(&config, &ticket_root, alias, branch);
+    }
+
+    let mut failures = Vec::new();
+    for alias in &aliases {
+        if let Err(e) = add_repo(&config, &ticket_root, alias, branch)
+            .with_context(|| format!("Failed to add '{}'", alias))
+        {
+            error!("{:#}", e);
+            failures.push(e);
+        }
+    }
+    combine_failures(failures, "Failed to add one or more repositories")
+}
+
+/// Resolved inputs for adding one repo worktree to a ticket.
+struct AddPlan<'a> {
+    alias: &'a str,
+    repo_def: &'a RepoDefinition,
+    ticket_meta: Ticket,
+    target_worktree: PathBuf,
+    branch_name: String,
+    base_ref: Option<&'a str>,
+}
+
+/// Add the worktree for a single alias.
+fn add_repo(
+    config: &Config,
+    ticket_root: &Path,
+    repo_alias: &str,
+    branch: Option<&str>,
+) -> Result<()> {
+    let plan = plan_add(config, ticket_root, repo_alias, branch)?;
+    apply(config, ticket_root, &plan)
+}
+
+/// Check that `repo_alias` can be added and work out its worktree path and branch.
+fn plan_add<'a>(
+    config: &'a Config,
+    ticket_root: &Path,
+    repo_alias: &'a str,
+    branch: Option<&'a str>,
+) -> Result<AddPlan<'a>> {
+    let ticket_meta = Ticket::load(ticket_root).context(
         "Failed to load ticket metadata. Run from a valid ticket directory or specify --ticket",
     )?;
 
@@ -32,19 +98,16 @@ pub fn run(repo_alias: &str, ticket: Option<&str>, branch: Option<&str>) -> Resu
         );
     }
 
-    let branch_name = build_branch_name(
-        &config,
-        &ticket_meta.metadata.id,
-        ticket_meta.metadata.description.as_ref(),
-    );
-    // Prefer recorded branch for this repo, then ticket branch, then computed branch
+    let branch_name = branch_name_for_metadata(config, &ticket_meta.metadata, Some(repo_def))?;
+    // Prefer recorded branch for this repo, then ticket branch (unless the repo overrides
+    // `branch_prefix`), then computed branch
     let branch_name = ticket_meta
         .metadata
         .repo_branches
         .get(repo_alias)
         .cloned()
         .or_else(|| {
-            if !ticket_meta.metadata.branch.is_empty() {
+            if !ticket_meta.metadata.branch.is_empty() && repo_def.branch_prefix.is_none() {
                 Some(ticket_meta.metadata.branch.clone())
             } else {
                 None
@@ -57,7 +120,59 @@ pub fn run(repo_alias: &str, ticket: Option<&str>, branch: Option<&str>) -> Resu
             repo_alias, branch_name
         );
     }
-    let base_ref = branch.map(|s| s.to_string());
+
+    Ok(AddPlan {
+        alias: repo_alias,
+        repo_def,
+        ticket_meta,
+        target_worktree,
+        branch_name,
+        base_ref: branch,
+    })
+}
+
+/// Actions `apply` would take for `plan`.
+fn describe(config: &Config, ticket_root: &Path, plan: &AddPlan) -> Result<Vec<Action>> {
+    let repo_def = plan.repo_def;
+    let mut actions = vec![Action::Fetch {
+        alias: plan.alias.to_string(),
+        repo: repo_def.path.clone(),
+    }];
+    if let Some(from) = git::new_branch_start_point(
+        &repo_def.path,
+        &plan.branch_name,
+        plan.base_ref,
+        &plan
+            .ticket_meta
+            .metadata
+            .remote_options(plan.alias, repo_def),
+    )? {
+        actions.push(Action::CreateBranch {
+            alias: plan.alias.to_string(),
+            branch: plan.branch_name.clone(),
+            from,
+        });
+    }
+    actions.push(Action::CreateWorktree {
+        alias: plan.alias.to_string(),
+        path: plan.target_worktree.clone(),
+        branch: plan.branch_name.clone(),
+    });
+    actions.push(Action::WriteMetadata(metadata_path(ticket_root)));
+    actions.extend(plan::hook_actions(
+        HookEvent::PostAdd,
+        config,
+        Some(repo_def),
+        plan.alias,
+    ));
+    Ok(actions)
+}
+
+fn apply(config: &Config, ticket_root: &Path, plan: &AddPlan) -> Result<()> {
+    let repo_alias = plan.alias;
+    let repo_def = plan.repo_def;
+    let target_worktree = &plan.target_worktree;
+    let branch_name = &plan.branch_name;
 
     info!(
         "Adding worktree for repo '{}' into {:?} on branch '{}'",
@@ -65,7 +180,11 @@ pub fn run(repo_alias: &str, ticket: Option<&str>, branch: Option<&str>) -> Resu
     );
 
     // Ensure repo is up to date before branching.
-    git::fetch_and_fast_forward(&repo_def.path, "origin").with_context(|| {
+    let remote = plan
+        .ticket_meta
+        .metadata
+        .remote_options(repo_alias, repo_def);
+    git::fetch_and_fast_forward(&repo_def.path, &remote).with_context(|| {
         format!(
             "Failed to update repo '{}' at {:?}",
             repo_alias, repo_def.path
@@ -74,16 +193,27 @@ pub fn run(repo_alias: &str, ticket: Option<&str>, branch: Option<&str>) -> Resu
 
     git::create_worktree(
         &repo_def.path,
-        &target_worktree,
-        &branch_name,
-        base_ref.as_deref(),
+        target_worktree,
+        branch_name,
+        plan.base_ref,
+        &remote,
     )
     .context("Failed to create worktree")?;
 
     info!("Created worktree at {:?}", target_worktree);
-    Ticket::ensure_branch(&ticket_root, &branch_name)?;
-    Ticket::add_repo_branch(&ticket_root, repo_alias, &branch_name)?;
-    Ok(())
+    Ticket::ensure_branch(ticket_root, branch_name)?;
+    Ticket::add_repo_branch(ticket_root, repo_alias, branch_name)?;
+    if let Some(carried) = carry::carry_into_new_worktree(repo_alias, repo_def, target_worktree) {
+        Ticket::set_carried_files(ticket_root, repo_alias, carried)?;
+    }
+
+    let target = HookTarget {
+        alias: repo_alias,
+        worktree: target_worktree,
+        ticket_root,
+        ticket_id: &plan.ticket_meta.metadata.id,
+    };
+    hooks::run(HookEvent::PostAdd, config, Some(repo_def), &target)
 }
 
 fn ensure_ticket_exists(ticket_dir: &Path) -> Result<()> {
@@ -1,6 +1,7 @@
 //! Register a repository in the configuration without cloning it.
 
 use crate::core::config::{Config, RepoDefinition};
+use crate::core::plan::{self, Action};
 use anyhow::{Context, Result, bail};
 use log::{debug, info, warn};
 
@@ -28,13 +29,14 @@ pub fn plan_repo_registration(
     let repo_def = RepoDefinition {
         url: parsed.url,
         path: local_path,
+        ..Default::default()
     };
 
     Ok((alias, repo_def))
 }
 
-/// Add a repository entry to config and save.
-pub fn run(repo_input: &str, alias: Option<String>) -> Result<()> {
+/// Add a repository entry to config and save. With `dry_run`, only print the entry.
+pub fn run(repo_input: &str, alias: Option<String>, dry_run: bool) -> Result<()> {
     let mut config = Config::load()?;
     let (alias, repo_def) = plan_repo_registration(&config, repo_input, alias.as_deref())?;
 
@@ -48,6 +50,19 @@ pub fn run(repo_input: &str, alias: Option<String>) -> Result<()> {
             alias
         );
     }
+    if dry_run {
+        plan::print(&[
+            Action::SetConfig {
+                key: format!("repositories.{}.url", alias),
+                value: repo_def.url,
+            },
+            Action::SetConfig {
+                key: format!("repositories.{}.path", alias),
+                value: repo_def.path.display().to_string(),
+            },
+        ]);
+        return Ok(());
+    }
     config.repositories.insert(alias.clone(), repo_def);
     config.save().context("Failed to save updated config")?;
 
@@ -158,6 +173,7 @@ mod tests {
             repositories: HashMap::new(),
             plugins: HashMap::new(),
             jira_base_url: None,
+            ..Default::default()
         }
     }
 
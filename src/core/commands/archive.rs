@@ -0,0 +1,115 @@
+//! Archive a ticket: record its worktrees, remove them, and keep the branches.
+
+use crate::core::carry;
+use crate::core::commands::destroy::{
+    dir_alias, ensure_not_inside, prune_worktrees, worktree_dirs,
+};
+use crate::core::config::Config;
+use crate::core::git;
+use crate::core::ticket::{ArchiveRecord, ArchivedRepo, Ticket, archive_root};
+use crate::core::ticket_id;
+use anyhow::{Context, Result, bail};
+use log::{info, warn};
+use std::fs;
+
+/// Run the archive command.
+pub fn run(ticket_id: &str, force: bool) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_id = &ticket_id::normalize(&config, ticket_id)?;
+    let ticket_dir = config.tickets_directory.join(ticket_id);
+    let archive_dir = archive_root(&config.tickets_directory).join(ticket_id);
+
+    if !ticket_dir.exists() {
+        bail!("Ticket directory {:?} does not exist", ticket_dir);
+    }
+    if archive_dir.exists() {
+        bail!(
+            "Ticket '{}' is already archived at {:?}; restore or delete it first",
+            ticket_id,
+            archive_dir
+        );
+    }
+    ensure_not_inside(&ticket_dir)?;
+
+    let ticket = Ticket::load(&ticket_dir).context("Failed to load ticket metadata")?;
+    let dirs = worktree_dirs(&ticket_dir);
+
+    // Uncommitted changes are not captured by the archive record.
+    if !force {
+        for dir in &dirs {
+            let carried = ticket.metadata.carried_files(&dir_alias(dir));
+            let is_clean = git::is_clean_ignoring(dir, carried)
+                .with_context(|| format!("Could not check clean status for {:?}", dir))?;
+            if !is_clean {
+                bail!(
+                    "Worktree at {:?} has uncommitted changes that would be lost. Commit them or use --force.",
+                    dir
+                );
+            }
+        }
+    }
+
+    let mut record = ArchiveRecord {
+        archived_at: chrono::Local::now().to_rfc3339(),
+        ..Default::default()
+    };
+    let mut aliases: Vec<String> = ticket.metadata.repo_branches.keys().cloned().collect();
+    aliases.sort();
+    for alias in &aliases {
+        let branch = ticket.metadata.repo_branches[alias].clone();
+        let head = match config.repositories.get(alias) {
+            Some(def) => git::branch_head(&def.path, &branch)?,
+            None => {
+                warn!(
+                    "Repo alias '{}' not found in config; archiving without a head commit",
+                    alias
+                );
+                None
+            }
+        };
+        let worktree_name = ticket
+            .metadata
+            .repo_worktrees
+            .get(alias)
+            .cloned()
+            .unwrap_or_else(|| crate::core::ticket::worktree_name_for_branch(&branch));
+        info!(
+            "[{}] Archiving branch '{}' at {}",
+            alias,
+            branch,
+            head.as_deref().unwrap_or("(unknown)")
+        );
+        record.repos.insert(
+            alias.clone(),
+            ArchivedRepo {
+                branch,
+                head,
+                worktree_name,
+            },
+        );
+    }
+    record.write(&ticket_dir)?;
+
+    for dir in &dirs {
+        info!("Removing worktree directory {:?}", dir);
+        carry::unlink_symlinks(dir, ticket.metadata.carried_files(&dir_alias(dir)))?;
+        fs::remove_dir_all(dir)
+            .with_context(|| format!("Failed to remove worktree directory {:?}", dir))?;
+    }
+    prune_worktrees(&config, ticket_id, Some(&ticket.metadata), &aliases)?;
+
+    // Move what is left (metadata, archive record, plugin state) into the archive store.
+    fs::create_dir_all(archive_root(&config.tickets_directory))?;
+    fs::rename(&ticket_dir, &archive_dir).with_context(|| {
+        format!(
+            "Failed to move ticket directory {:?} to {:?}",
+            ticket_dir, archive_dir
+        )
+    })?;
+
+    info!(
+        "Archived ticket '{}' to {:?}; branches were kept. Use 'tix restore {}' to bring it back.",
+        ticket_id, archive_dir, ticket_id
+    );
+    Ok(())
+}
@@ -1,21 +1,98 @@
 //! Shared helpers for commands to reduce drift.
 
-use crate::core::config::Config;
-use anyhow::{Result, bail};
+use crate::core::branch;
+use crate::core::config::{Config, RepoDefinition};
+use crate::core::git;
+use crate::core::ticket::TicketMetadata;
+use crate::core::ticket_id;
+use anyhow::{Context, Result, bail};
+use log::{debug, warn};
+use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
 
-/// Build the default branch name for a ticket (with optional description).
-pub fn build_branch_name(config: &Config, ticket_id: &str, description: Option<&String>) -> String {
-    let mut branch_name = format!("{}/{}", config.branch_prefix, ticket_id);
-    if let Some(desc) = description {
-        let sanitized = sanitize_description(desc);
-        if !sanitized.is_empty() {
-            branch_name.push('-');
-            branch_name.push_str(&sanitized);
-        }
+/// Maximum number of commits listed per branch when refusing a destructive command.
+const MAX_LISTED_COMMITS: usize = 10;
+
+/// Build the branch name for a new ticket from the configured branch template.
+///
+/// Pass `repo` to honour its `branch_prefix` override.
+pub fn build_branch_name(
+    config: &Config,
+    ticket_id: &str,
+    description: Option<&String>,
+    ticket_type: Option<&str>,
+    repo: Option<&RepoDefinition>,
+) -> Result<String> {
+    render_branch_name(
+        config,
+        repo,
+        ticket_id,
+        description,
+        ticket_type,
+        chrono::Local::now().date_naive(),
+    )
+}
+
+/// Recompute the branch name for an existing ticket, using its creation date for `{date}`.
+pub fn branch_name_for_metadata(
+    config: &Config,
+    metadata: &TicketMetadata,
+    repo: Option<&RepoDefinition>,
+) -> Result<String> {
+    let date = chrono::DateTime::parse_from_rfc3339(&metadata.created_at)
+        .map(|d| d.date_naive())
+        .unwrap_or_else(|_| chrono::Local::now().date_naive());
+    render_branch_name(
+        config,
+        repo,
+        &metadata.id,
+        metadata.description.as_ref(),
+        metadata.ticket_type.as_deref(),
+        date,
+    )
+}
+
+fn render_branch_name(
+    config: &Config,
+    repo: Option<&RepoDefinition>,
+    ticket_id: &str,
+    description: Option<&String>,
+    ticket_type: Option<&str>,
+    date: chrono::NaiveDate,
+) -> Result<String> {
+    let prefix = repo
+        .and_then(|r| r.branch_prefix.as_deref())
+        .unwrap_or(&config.branch_prefix);
+    let template = config.branch_template_for(ticket_type);
+    let slug = description
+        .map(|d| branch::truncate_slug(&sanitize_description(d), config.branch_slug_max_length))
+        .unwrap_or_default();
+
+    let mut values: HashMap<&str, String> = HashMap::new();
+    values.insert("prefix", prefix.to_string());
+    values.insert("ticket", ticket_id.to_string());
+    values.insert("slug", slug);
+    values.insert("date", date.format("%Y%m%d").to_string());
+    values.insert("type", ticket_type.unwrap_or(prefix).to_string());
+    if template.contains("{user}") {
+        values.insert("user", branch_user());
     }
-    branch_name
+    branch::render(template, &values)
+}
+
+/// Resolve `{user}`: git `github.user`, then `user.name`, then `$USER`, sanitized for branch names.
+fn branch_user() -> String {
+    let from_git = git2::Config::open_default().ok().and_then(|cfg| {
+        cfg.get_string("github.user")
+            .or_else(|_| cfg.get_string("user.name"))
+            .ok()
+    });
+    let raw = from_git
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .unwrap_or_default();
+    sanitize_description(&raw)
 }
 
 /// Sanitize free-form text for inclusion in a git branch name (lowercase, alnum, single hyphens).
@@ -42,10 +119,21 @@ pub fn sanitize_description(input: &str) -> String {
     result
 }
 
+/// Fold per-repo failures into a single error whose message lists every failure.
+/// Returns `Ok(())` when `failures` is empty.
+pub fn combine_failures(failures: Vec<anyhow::Error>, headline: &str) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let summaries: Vec<String> = failures.iter().map(|e| format!("- {:#}", e)).collect();
+    let first = failures.into_iter().next().expect("failures is not empty");
+    Err(first.context(format!("{}:\n{}", headline, summaries.join("\n"))))
+}
+
 /// Locate the ticket root for a command, either from a provided id or by walking up.
 pub fn locate_ticket_root(ticket: Option<&str>, config: &Config) -> Result<PathBuf> {
     if let Some(id) = ticket {
-        return Ok(config.tickets_directory.join(id));
+        return ticket_id::ticket_dir(config, id);
     }
 
     if let Some(dir) = find_ticket_root_from_cwd() {
@@ -70,10 +158,93 @@ fn find_ticket_root_from_cwd() -> Option<PathBuf> {
     None
 }
 
+/// Refuse to continue when any of `aliases` has commits that are neither pushed nor merged.
+///
+/// `action` names the command in the error (e.g., "destroy"). Aliases without a registered repo
+/// or recorded branch are skipped with a warning.
+pub fn ensure_branches_published(
+    config: &Config,
+    metadata: &TicketMetadata,
+    aliases: &[String],
+    action: &str,
+) -> Result<()> {
+    let mut findings = Vec::new();
+    for alias in aliases {
+        let Some(repo_def) = config.repositories.get(alias) else {
+            warn!(
+                "Repo alias '{}' not found in config; skipping unpushed-commit check",
+                alias
+            );
+            continue;
+        };
+        let Some(branch) = metadata.repo_branches.get(alias) else {
+            warn!(
+                "No stored branch for repo '{}'; skipping unpushed-commit check",
+                alias
+            );
+            continue;
+        };
+        let base =
+            git::default_branch_for(&repo_def.path, &metadata.remote_options(alias, repo_def));
+        let unpublished = git::unpublished_commits(&repo_def.path, branch, base.as_deref())
+            .with_context(|| {
+                format!(
+                    "Could not check branch '{}' for unpushed commits in repo '{}'",
+                    branch, alias
+                )
+            })?;
+        debug!(
+            "Repo '{}' branch '{}' has {} unpublished commit(s)",
+            alias,
+            branch,
+            unpublished.commits.len()
+        );
+        if !unpublished.commits.is_empty() {
+            findings.push(format_unpublished(alias, branch, &unpublished));
+        }
+    }
+
+    if !findings.is_empty() {
+        bail!(
+            "Refusing to {}: found commits that are not pushed or merged:\n{}\nPush or merge them first, or re-run with --allow-unpushed.",
+            action,
+            findings.join("\n")
+        );
+    }
+    Ok(())
+}
+
+fn format_unpublished(alias: &str, branch: &str, unpublished: &git::UnpublishedCommits) -> String {
+    let mut out = format!(
+        "- {} ({}; upstream: {}; base: {}): {} commit(s)",
+        alias,
+        branch,
+        unpublished.upstream.as_deref().unwrap_or("none"),
+        unpublished.base.as_deref().unwrap_or("unknown"),
+        unpublished.commits.len()
+    );
+    for commit in unpublished.commits.iter().take(MAX_LISTED_COMMITS) {
+        out.push_str(&format!("\n    {} {}", commit.id, commit.summary));
+    }
+    if unpublished.commits.len() > MAX_LISTED_COMMITS {
+        out.push_str(&format!(
+            "\n    ... and {} more",
+            unpublished.commits.len() - MAX_LISTED_COMMITS
+        ));
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{build_branch_name, locate_ticket_root, sanitize_description};
-    use crate::core::{config::Config, defaults};
+    use super::{
+        branch_name_for_metadata, build_branch_name, format_unpublished, locate_ticket_root,
+        sanitize_description,
+    };
+    use crate::core::config::{Config, RepoDefinition};
+    use crate::core::defaults;
+    use crate::core::git::{CommitSummary, UnpublishedCommits};
+    use crate::core::ticket::TicketMetadata;
     use std::collections::HashMap;
     use std::env;
     use std::fs;
@@ -90,6 +261,7 @@ mod tests {
             repositories: HashMap::new(),
             plugins: HashMap::new(),
             jira_base_url: None,
+            ..Default::default()
         }
     }
 
@@ -97,10 +269,47 @@ mod tests {
     fn branch_name_includes_description() {
         let cfg = base_config();
         let desc = "Short Summary".to_string();
-        let name = build_branch_name(&cfg, "JIRA-1", Some(&desc));
+        let name = build_branch_name(&cfg, "JIRA-1", Some(&desc), None, None).unwrap();
         assert_eq!(name, "feature/JIRA-1-short-summary");
     }
 
+    #[test]
+    fn branch_name_uses_template_for_ticket_type() {
+        let mut cfg = base_config();
+        cfg.branch_template = Some("{prefix}/{ticket}/{slug}".into());
+        cfg.branch_templates
+            .insert("bugfix".into(), "fix/{date}/{ticket}-{slug}".into());
+        cfg.branch_slug_max_length = Some(5);
+        let desc = "Short Summary".to_string();
+
+        let name = build_branch_name(&cfg, "JIRA-1", Some(&desc), None, None).unwrap();
+        assert_eq!(name, "feature/JIRA-1/short");
+
+        let metadata = TicketMetadata {
+            id: "JIRA-2".into(),
+            description: Some(desc),
+            created_at: "2024-03-05T10:00:00+00:00".into(),
+            ticket_type: Some("bugfix".into()),
+            ..Default::default()
+        };
+        let name = branch_name_for_metadata(&cfg, &metadata, None).unwrap();
+        assert_eq!(name, "fix/20240305/JIRA-2-short");
+    }
+
+    #[test]
+    fn branch_name_honours_repo_prefix_override() {
+        let cfg = base_config();
+        let repo = RepoDefinition {
+            branch_prefix: Some("users/jdoe".into()),
+            ..Default::default()
+        };
+        let name = build_branch_name(&cfg, "JIRA-1", None, None, Some(&repo)).unwrap();
+        assert_eq!(name, "users/jdoe/JIRA-1");
+        let name = build_branch_name(&cfg, "JIRA-1", None, None, Some(&RepoDefinition::default()))
+            .unwrap();
+        assert_eq!(name, "feature/JIRA-1");
+    }
+
     #[test]
     fn locate_ticket_root_walks_upwards() {
         static CWD_LOCK: Mutex<()> = Mutex::new(());
@@ -128,4 +337,25 @@ mod tests {
             "feat-payment-auth"
         );
     }
+
+    #[test]
+    fn format_unpublished_lists_commits() {
+        let unpublished = UnpublishedCommits {
+            upstream: None,
+            base: Some("main".into()),
+            commits: (0..12)
+                .map(|i| CommitSummary {
+                    id: format!("abc{}", i),
+                    summary: format!("Commit {}", i),
+                })
+                .collect(),
+        };
+        let text = format_unpublished("api", "feature/JIRA-1", &unpublished);
+        assert!(
+            text.starts_with("- api (feature/JIRA-1; upstream: none; base: main): 12 commit(s)")
+        );
+        assert!(text.contains("abc0 Commit 0"));
+        assert!(!text.contains("abc11 Commit 11"));
+        assert!(text.ends_with("... and 2 more"));
+    }
 }
@@ -1,9 +1,12 @@
 //! View or set configuration values.
 
-use crate::core::config::Config;
+use crate::core::commands::repair;
+use crate::core::config::{Config, TicketIdCase};
 use anyhow::{Context, Result, bail};
+use dialoguer::Confirm;
 use log::{debug, info, warn};
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Set a key to a value or show the current value if `value` is None.
@@ -16,7 +19,7 @@ pub fn run(key: Option<&str>, value: Option<&str>, edit: bool) -> Result<()> {
 
     let config_path = Config::config_path()?;
     debug!("Loading config from {:?}", config_path);
-    let mut config = Config::load()?;
+    let mut config = Config::load_unvalidated()?;
 
     if edit && key.is_none() {
         ensure_config_file(&config)?;
@@ -31,6 +34,8 @@ pub fn run(key: Option<&str>, value: Option<&str>, edit: bool) -> Result<()> {
     }
 
     let key = key.unwrap();
+    let previous_code_directory = config.code_directory.clone();
+    let previous_tickets_directory = config.tickets_directory.clone();
     match key {
         "branch_prefix" => set_string(&mut config.branch_prefix, key, value)?,
         "github_base_url" => set_string(&mut config.github_base_url, key, value)?,
@@ -38,14 +43,72 @@ pub fn run(key: Option<&str>, value: Option<&str>, edit: bool) -> Result<()> {
         "code_directory" => set_path(&mut config.code_directory, key, value)?,
         "tickets_directory" => set_path(&mut config.tickets_directory, key, value)?,
         "jira_base_url" => set_optional_string(&mut config.jira_base_url, key, value)?,
+        "max_parallel_jobs" => set_optional_usize(&mut config.max_parallel_jobs, key, value)?,
+        "branch_template" => set_optional_string(&mut config.branch_template, key, value)?,
+        "branch_slug_max_length" => {
+            set_optional_usize(&mut config.branch_slug_max_length, key, value)?
+        }
+        "ticket_id_pattern" => set_optional_string(&mut config.ticket_id_pattern, key, value)?,
+        "ticket_id_case" => set_ticket_id_case(&mut config.ticket_id_case, key, value)?,
         other => bail!("Unknown config key '{}'", other),
     }
 
     if value.is_some() {
+        let code_directory_moved = config.code_directory != previous_code_directory;
+        if code_directory_moved {
+            let code_directory = config.code_directory.clone();
+            for (alias, from, to) in
+                move_repo_paths(&mut config, &previous_code_directory, &code_directory)
+            {
+                info!("Repo '{}' moved: {:?} -> {:?}", alias, from, to);
+            }
+        }
+        if let Err(e) = config.validate() {
+            bail!("{:#}", e);
+        }
         config.save().context("Failed to save config")?;
         info!("Updated '{}'", key);
+
+        if code_directory_moved || config.tickets_directory != previous_tickets_directory {
+            offer_repair(&config)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Point repos that lived under `from` at the same place under `to`, but only when the repo
+/// is actually found at the new location (and no longer at the old one).
+/// Returns `(alias, old path, new path)` for every repo that was updated.
+fn move_repo_paths(config: &mut Config, from: &Path, to: &Path) -> Vec<(String, PathBuf, PathBuf)> {
+    let mut moved = Vec::new();
+    for (alias, repo) in config.repositories.iter_mut() {
+        let Ok(relative) = repo.path.strip_prefix(from) else {
+            continue;
+        };
+        let candidate = to.join(relative);
+        if !repo.path.exists() && candidate.exists() {
+            let old = std::mem::replace(&mut repo.path, candidate.clone());
+            moved.push((alias.clone(), old, candidate));
+        }
     }
+    moved.sort();
+    moved
+}
 
+/// Worktree links use absolute paths, so moving either directory breaks them: offer to run
+/// `tix repair` right away when attached to a terminal, otherwise print a hint.
+fn offer_repair(config: &Config) -> Result<()> {
+    let interactive = io::stdin().is_terminal() && io::stderr().is_terminal();
+    if interactive
+        && Confirm::new()
+            .with_prompt("Re-link ticket worktrees to the new location now?")
+            .default(true)
+            .interact()?
+    {
+        return repair::repair_all(config);
+    }
+    info!("Hint: Run 'tix repair' if you moved existing tickets or repositories.");
     Ok(())
 }
 
@@ -74,6 +137,44 @@ fn set_optional_string(field: &mut Option<String>, key: &str, value: Option<&str
     Ok(())
 }
 
+fn set_optional_usize(field: &mut Option<usize>, key: &str, value: Option<&str>) -> Result<()> {
+    if let Some(val) = value {
+        if val.trim().is_empty() {
+            *field = None;
+        } else {
+            let parsed: usize = val
+                .trim()
+                .parse()
+                .with_context(|| format!("{} must be a positive integer", key))?;
+            if parsed == 0 {
+                bail!("{} must be at least 1", key);
+            }
+            *field = Some(parsed);
+        }
+    } else {
+        match field {
+            Some(n) => info!("{} = {}", key, n),
+            None => info!("{} = (not set)", key),
+        }
+    }
+    Ok(())
+}
+
+fn set_ticket_id_case(field: &mut TicketIdCase, key: &str, value: Option<&str>) -> Result<()> {
+    match value.map(str::trim) {
+        Some("preserve") | Some("") => *field = TicketIdCase::Preserve,
+        Some("upper") => *field = TicketIdCase::Upper,
+        Some("lower") => *field = TicketIdCase::Lower,
+        Some(other) => bail!(
+            "{} must be one of preserve, upper, lower (got '{}')",
+            key,
+            other
+        ),
+        None => info!("{} = {:?}", key, field),
+    }
+    Ok(())
+}
+
 fn set_path(field: &mut PathBuf, key: &str, value: Option<&str>) -> Result<()> {
     if let Some(val) = value {
         if val.trim().is_empty() {
@@ -117,7 +218,7 @@ fn ensure_config_file(config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn open_in_editor(path: &PathBuf) -> Result<()> {
+fn open_in_editor(path: &Path) -> Result<()> {
     let editor = std::env::var("EDITOR").map_err(|_| {
         anyhow::anyhow!("$EDITOR is not set; set it or run `tix config` to view the file")
     })?;
@@ -128,7 +229,7 @@ fn open_in_editor(path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn spawn_editor(editor: &str, path: &PathBuf) -> Result<std::process::ExitStatus> {
+fn spawn_editor(editor: &str, path: &Path) -> Result<std::process::ExitStatus> {
     let path_str = path.display().to_string();
     if cfg!(windows) {
         let cmd = format!("{} \"{}\"", editor, path_str);
@@ -161,7 +262,8 @@ fn shell_escape(value: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::{config::Config, defaults};
+    use crate::core::config::{Config, RepoDefinition, TicketIdCase};
+    use crate::core::defaults;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -175,6 +277,7 @@ mod tests {
             repositories: HashMap::new(),
             plugins: HashMap::new(),
             jira_base_url: None,
+            ..Default::default()
         }
     }
 
@@ -196,6 +299,62 @@ mod tests {
         assert_eq!(config.code_directory, expected_prefix);
     }
 
+    #[test]
+    fn max_parallel_jobs_parses_and_rejects_zero() {
+        let mut jobs = None;
+        super::set_optional_usize(&mut jobs, "max_parallel_jobs", Some("6")).unwrap();
+        assert_eq!(jobs, Some(6));
+        assert!(super::set_optional_usize(&mut jobs, "max_parallel_jobs", Some("0")).is_err());
+        assert!(super::set_optional_usize(&mut jobs, "max_parallel_jobs", Some("many")).is_err());
+        super::set_optional_usize(&mut jobs, "max_parallel_jobs", Some("")).unwrap();
+        assert_eq!(jobs, None);
+    }
+
+    #[test]
+    fn ticket_id_case_accepts_known_policies() {
+        let mut case = TicketIdCase::Preserve;
+        super::set_ticket_id_case(&mut case, "ticket_id_case", Some("upper")).unwrap();
+        assert_eq!(case, TicketIdCase::Upper);
+        assert!(super::set_ticket_id_case(&mut case, "ticket_id_case", Some("title")).is_err());
+        super::set_ticket_id_case(&mut case, "ticket_id_case", Some("")).unwrap();
+        assert_eq!(case, TicketIdCase::Preserve);
+    }
+
+    #[test]
+    fn move_repo_paths_follows_repos_that_moved() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let old = temp.path().join("old");
+        let new = temp.path().join("new");
+        std::fs::create_dir_all(new.join("api")).unwrap();
+        std::fs::create_dir_all(old.join("web")).unwrap();
+        let mut config = base_config();
+        for alias in ["api", "web"] {
+            config.repositories.insert(
+                alias.into(),
+                RepoDefinition {
+                    path: old.join(alias),
+                    ..Default::default()
+                },
+            );
+        }
+        config.repositories.insert(
+            "other".into(),
+            RepoDefinition {
+                path: temp.path().join("elsewhere/other"),
+                ..Default::default()
+            },
+        );
+
+        let moved = super::move_repo_paths(&mut config, &old, &new);
+        assert_eq!(
+            moved,
+            vec![("api".into(), old.join("api"), new.join("api"))]
+        );
+        assert_eq!(config.repositories["api"].path, new.join("api"));
+        // Not moved on disk, so it keeps pointing at the old location.
+        assert_eq!(config.repositories["web"].path, old.join("web"));
+    }
+
     #[test]
     fn unknown_key_errors() {
         let mut config = base_config();
@@ -1,18 +1,33 @@
 //! Destroy a ticket workspace after safety checks.
 
-use crate::core::commands::common::build_branch_name;
+use crate::core::carry;
+use crate::core::commands::common::{
+    build_branch_name, combine_failures, ensure_branches_published,
+};
 use crate::core::config::Config;
 use crate::core::git;
-use crate::core::ticket::Ticket;
+use crate::core::hooks::{self, HookEvent, HookTarget};
+use crate::core::plan::{self, Action};
+use crate::core::ticket::{Ticket, TicketMetadata};
+use crate::core::ticket_id;
 use anyhow::{Context, Result, anyhow, bail};
-use log::{debug, info, warn};
+use log::{debug, error, info, warn};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-/// Run the destroy command.
-pub fn run(ticket_id: &str, force: bool) -> Result<()> {
+/// Run the destroy command. With `dry_run`, the safety checks still run but only the plan is
+/// printed.
+pub fn run(
+    ticket_id: &str,
+    force: bool,
+    allow_unpushed: bool,
+    delete_branches: bool,
+    delete_remote_branches: bool,
+    dry_run: bool,
+) -> Result<()> {
     let config = Config::load()?;
+    let ticket_id = &ticket_id::normalize(&config, ticket_id)?;
     let ticket_dir = config.tickets_directory.join(ticket_id);
 
     if !ticket_dir.exists() {
@@ -44,7 +59,8 @@ pub fn run(ticket_id: &str, force: bool) -> Result<()> {
     // Safety checks: ensure clean unless --force
     if !force {
         for dir in &worktree_dirs {
-            let is_clean = git::is_clean(dir)
+            let carried = carried_files(ticket_meta.as_ref(), dir);
+            let is_clean = git::is_clean_ignoring(dir, carried)
                 .with_context(|| format!("Could not check clean status for {:?}", dir))?;
             if !is_clean {
                 return Err(anyhow!(
@@ -55,25 +71,337 @@ pub fn run(ticket_id: &str, force: bool) -> Result<()> {
         }
     }
 
+    // Safety checks: refuse to orphan commits that were never pushed or merged
+    if !force && !allow_unpushed {
+        match ticket_meta.as_ref() {
+            Some(meta) => ensure_branches_published(&config, meta, &aliases_to_prune, "destroy")?,
+            None => warn!("No ticket metadata; skipping unpushed-commit check"),
+        }
+    }
+
+    let delete_any = delete_branches || delete_remote_branches;
+    if dry_run {
+        let mut actions = describe_removal(&config, ticket_id, &ticket_dir, ticket_meta.as_ref())?;
+        match ticket_meta.as_ref() {
+            Some(meta) if delete_any => actions.extend(describe_branch_deletions(
+                &config,
+                meta,
+                force,
+                delete_branches,
+                delete_remote_branches,
+            )?),
+            None if delete_any => {
+                warn!("No ticket metadata; cannot tell which branches to delete")
+            }
+            _ => {}
+        }
+        plan::print(&actions);
+        return Ok(());
+    }
+
+    remove_ticket(&config, ticket_id, &ticket_dir, ticket_meta.as_ref())?;
+    info!("Destroyed ticket '{}'", ticket_id);
+
+    if !delete_any {
+        return Ok(());
+    }
+    match ticket_meta.as_ref() {
+        Some(meta) => delete_ticket_branches(
+            &config,
+            meta,
+            force,
+            delete_branches,
+            delete_remote_branches,
+        ),
+        None => {
+            warn!("No ticket metadata; cannot tell which branches to delete");
+            Ok(())
+        }
+    }
+}
+
+/// Actions `remove_ticket` would take.
+fn describe_removal(
+    config: &Config,
+    ticket_id: &str,
+    ticket_dir: &Path,
+    ticket_meta: Option<&TicketMetadata>,
+) -> Result<Vec<Action>> {
+    let worktree_dirs = worktree_dirs(ticket_dir);
+    let aliases_to_prune = aliases_to_prune(&worktree_dirs, ticket_meta);
+
+    let mut actions = Vec::new();
+    for dir in &worktree_dirs {
+        let alias = dir_alias(dir);
+        actions.extend(plan::hook_actions(
+            HookEvent::PreDestroy,
+            config,
+            config.repositories.get(&alias),
+            &alias,
+        ));
+    }
+    actions.extend(worktree_dirs.into_iter().map(Action::RemoveDir));
+    for prune in plan_prune(config, ticket_id, ticket_meta, &aliases_to_prune)? {
+        actions.push(Action::PruneWorktree {
+            alias: prune.alias,
+            name: prune.worktree_name,
+        });
+    }
+    actions.push(Action::RemoveDir(ticket_dir.to_path_buf()));
+    Ok(actions)
+}
+
+/// What `delete_ticket_branches` would delete or keep, judged from the remote-tracking refs as of
+/// the last fetch.
+fn describe_branch_deletions(
+    config: &Config,
+    meta: &TicketMetadata,
+    force: bool,
+    local: bool,
+    remote: bool,
+) -> Result<Vec<Action>> {
+    let mut aliases: Vec<&String> = meta.repo_branches.keys().collect();
+    aliases.sort();
+
+    let mut actions = Vec::new();
+    for alias in aliases {
+        let branch = &meta.repo_branches[alias];
+        let Some(repo_def) = config.repositories.get(alias) else {
+            actions.push(Action::Keep {
+                alias: alias.clone(),
+                what: format!("branch '{}'", branch),
+                reason: "not in config".to_string(),
+            });
+            continue;
+        };
+        let options = meta.remote_options(alias, repo_def);
+        let base = git::sync_base_ref(&repo_def.path, &options);
+
+        let local_ref = format!("refs/heads/{}", branch);
+        if local && git::has_reference(&repo_def.path, &local_ref) {
+            actions.push(
+                match keep_reason(&repo_def.path, &local_ref, force, base.as_deref())? {
+                    None => Action::DeleteBranch {
+                        alias: alias.clone(),
+                        branch: branch.clone(),
+                    },
+                    Some(reason) => Action::Keep {
+                        alias: alias.clone(),
+                        what: format!("branch '{}'", branch),
+                        reason,
+                    },
+                },
+            );
+        }
+        let tracking_ref = format!("refs/remotes/{}/{}", options.remote, branch);
+        if remote && git::has_reference(&repo_def.path, &tracking_ref) {
+            actions.push(
+                match keep_reason(&repo_def.path, &tracking_ref, force, base.as_deref())? {
+                    None => Action::DeleteRemoteBranch {
+                        alias: alias.clone(),
+                        remote: options.remote.to_string(),
+                        branch: branch.clone(),
+                    },
+                    Some(reason) => Action::Keep {
+                        alias: alias.clone(),
+                        what: format!("branch '{}' on {}", branch, options.remote),
+                        reason,
+                    },
+                },
+            );
+        }
+    }
+    Ok(actions)
+}
+
+/// Delete each branch in `repo_branches` locally and/or on its remote once it is merged into the
+/// repo's base branch (unconditionally with `force`), then log what happened per repo.
+fn delete_ticket_branches(
+    config: &Config,
+    meta: &TicketMetadata,
+    force: bool,
+    local: bool,
+    remote: bool,
+) -> Result<()> {
+    let mut aliases: Vec<&String> = meta.repo_branches.keys().collect();
+    aliases.sort();
+
+    let mut summary = Vec::new();
+    let mut failures = Vec::new();
+    for alias in aliases {
+        let branch = &meta.repo_branches[alias];
+        let Some(repo_def) = config.repositories.get(alias) else {
+            summary.push(format!("{} ({}): not in config; skipped", alias, branch));
+            continue;
+        };
+        let options = meta.remote_options(alias, repo_def);
+        let base = git::sync_base_ref(&repo_def.path, &options);
+
+        let mut outcomes = Vec::new();
+        if local {
+            let outcome = delete_local_branch(&repo_def.path, branch, force, base.as_deref());
+            outcomes.push(format!(
+                "local {}",
+                outcome_text(outcome, alias, &mut failures)
+            ));
+        }
+        if remote {
+            let outcome = if !force && base.is_none() {
+                Ok("kept (base branch unknown)".to_string())
+            } else {
+                let merged_into = if force { None } else { base.as_deref() };
+                git::delete_remote_branch(&repo_def.path, options.remote, branch, merged_into).map(
+                    |deletion| match deletion {
+                        git::RemoteBranchDeletion::Deleted => "deleted".to_string(),
+                        git::RemoteBranchDeletion::NotFound => "not found".to_string(),
+                        git::RemoteBranchDeletion::NotMerged => {
+                            format!("kept ({})", not_merged(merged_into.unwrap_or_default()))
+                        }
+                    },
+                )
+            };
+            outcomes.push(format!(
+                "{} {}",
+                options.remote,
+                outcome_text(outcome, alias, &mut failures)
+            ));
+        }
+        summary.push(format!("{} ({}): {}", alias, branch, outcomes.join(", ")));
+    }
+
+    info!("Branches:");
+    for line in &summary {
+        info!("  {}", line);
+    }
+    combine_failures(failures, "Failed to delete one or more branches")
+}
+
+fn delete_local_branch(
+    repo_path: &Path,
+    branch: &str,
+    force: bool,
+    base: Option<&str>,
+) -> Result<String> {
+    let local_ref = format!("refs/heads/{}", branch);
+    if !git::has_reference(repo_path, &local_ref) {
+        return Ok("not found".to_string());
+    }
+    if let Some(reason) = keep_reason(repo_path, &local_ref, force, base)? {
+        return Ok(format!("kept ({})", reason));
+    }
+    git::delete_local_branch(repo_path, branch)?;
+    Ok("deleted".to_string())
+}
+
+/// Why the branch at `reference` has to be kept, or `None` when it may be deleted.
+fn keep_reason(
+    repo_path: &Path,
+    reference: &str,
+    force: bool,
+    base: Option<&str>,
+) -> Result<Option<String>> {
+    if force {
+        return Ok(None);
+    }
+    let Some(base) = base else {
+        return Ok(Some("base branch unknown".to_string()));
+    };
+    if git::is_merged(repo_path, reference, base)? {
+        Ok(None)
+    } else {
+        Ok(Some(not_merged(base)))
+    }
+}
+
+fn not_merged(base: &str) -> String {
+    format!("not merged into {}", git::short_ref_name(base))
+}
+
+/// Summary text for one deletion, recording failures for the final error.
+fn outcome_text(outcome: Result<String>, alias: &str, failures: &mut Vec<anyhow::Error>) -> String {
+    match outcome {
+        Ok(text) => text,
+        Err(e) => {
+            error!("[{}] {:#}", alias, e);
+            let text = format!("failed ({:#})", e);
+            failures.push(e.context(format!("Repo '{}'", alias)));
+            text
+        }
+    }
+}
+
+/// Run `pre_destroy` hooks, then remove the ticket's worktrees, their registrations in the source
+/// repos and the ticket directory. Safety checks are the caller's job.
+pub fn remove_ticket(
+    config: &Config,
+    ticket_id: &str,
+    ticket_dir: &Path,
+    ticket_meta: Option<&TicketMetadata>,
+) -> Result<()> {
+    let worktree_dirs = worktree_dirs(ticket_dir);
+    let aliases_to_prune = aliases_to_prune(&worktree_dirs, ticket_meta);
+
+    run_pre_destroy_hooks(config, ticket_id, ticket_dir, &worktree_dirs)?;
+
     // Remove directories
     for dir in &worktree_dirs {
         if dir.exists() {
             info!("Removing worktree directory {:?}", dir);
+            carry::unlink_symlinks(dir, carried_files(ticket_meta, dir))?;
             fs::remove_dir_all(dir)
                 .with_context(|| format!("Failed to remove worktree directory {:?}", dir))?;
         }
     }
 
-    prune_worktrees(&config, ticket_id, ticket_meta.as_ref(), &aliases_to_prune)?;
+    prune_worktrees(config, ticket_id, ticket_meta, &aliases_to_prune)?;
 
     info!("Removing ticket directory {:?}", ticket_dir);
-    fs::remove_dir_all(&ticket_dir)
+    fs::remove_dir_all(ticket_dir)
         .with_context(|| format!("Failed to remove ticket directory {:?}", ticket_dir))?;
-    info!("Destroyed ticket '{}'", ticket_id);
     Ok(())
 }
 
-fn worktree_dirs(ticket_dir: &Path) -> Vec<PathBuf> {
+/// Run `pre_destroy` hooks in every worktree that still exists.
+fn run_pre_destroy_hooks(
+    config: &Config,
+    ticket_id: &str,
+    ticket_dir: &Path,
+    worktree_dirs: &[PathBuf],
+) -> Result<()> {
+    for dir in worktree_dirs {
+        let Some(alias) = dir.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let target = HookTarget {
+            alias,
+            worktree: dir,
+            ticket_root: ticket_dir,
+            ticket_id,
+        };
+        hooks::run(
+            HookEvent::PreDestroy,
+            config,
+            config.repositories.get(alias),
+            &target,
+        )?;
+    }
+    Ok(())
+}
+
+/// Repo alias for a worktree directory inside a ticket (its directory name).
+pub fn dir_alias(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn carried_files<'a>(meta: Option<&'a TicketMetadata>, dir: &Path) -> &'a [PathBuf] {
+    meta.map(|m| m.carried_files(&dir_alias(dir)))
+        .unwrap_or_default()
+}
+
+/// Repo worktree directories inside a ticket (every subdirectory except `.tix`).
+pub fn worktree_dirs(ticket_dir: &Path) -> Vec<PathBuf> {
     let mut dirs = Vec::new();
     if let Ok(read) = fs::read_dir(ticket_dir) {
         for entry in read.flatten() {
@@ -86,7 +414,8 @@ fn worktree_dirs(ticket_dir: &Path) -> Vec<PathBuf> {
     dirs
 }
 
-fn ensure_not_inside(ticket_dir: &Path) -> Result<()> {
+/// Refuse to operate on `ticket_dir` while the current directory is inside it.
+pub fn ensure_not_inside(ticket_dir: &Path) -> Result<()> {
     let current = env::current_dir().context("Failed to get current directory")?;
     let ticket_canon = ticket_dir
         .canonicalize()
@@ -95,7 +424,7 @@ fn ensure_not_inside(ticket_dir: &Path) -> Result<()> {
 
     if current_canon.starts_with(&ticket_canon) {
         bail!(
-            "Refusing to destroy the ticket while you are inside {:?}",
+            "Refusing to modify the ticket while you are inside {:?}",
             ticket_dir
         );
     }
@@ -119,12 +448,43 @@ fn aliases_to_prune(
     aliases
 }
 
-fn prune_worktrees(
+/// A worktree registration to prune from a source repo.
+struct WorktreePrune {
+    alias: String,
+    repo_path: PathBuf,
+    worktree_name: String,
+}
+
+/// Prune the source-repo worktree registrations for `aliases`, preferring stored names.
+pub fn prune_worktrees(
     config: &Config,
     ticket_id: &str,
     meta: Option<&crate::core::ticket::TicketMetadata>,
     aliases: &[String],
 ) -> Result<()> {
+    for prune in plan_prune(config, ticket_id, meta, aliases)? {
+        debug!(
+            "Pruning worktree metadata '{}' in repo {:?}",
+            prune.worktree_name, prune.repo_path
+        );
+        git::remove_worktree(&prune.repo_path, &prune.worktree_name).with_context(|| {
+            format!(
+                "Failed to prune worktree '{}' for repo '{}' at {:?}",
+                prune.worktree_name, prune.alias, prune.repo_path
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Resolve the worktree registration of each of `aliases`, skipping aliases not in config.
+fn plan_prune(
+    config: &Config,
+    ticket_id: &str,
+    meta: Option<&crate::core::ticket::TicketMetadata>,
+    aliases: &[String],
+) -> Result<Vec<WorktreePrune>> {
+    let mut prunes = Vec::new();
     for alias in aliases {
         let repo_def = match config.repositories.get(alias) {
             Some(def) => def,
@@ -137,17 +497,20 @@ fn prune_worktrees(
             }
         };
 
-        let branch = meta
+        let stored = meta
             .and_then(|m| m.repo_branches.get(alias))
             .cloned()
-            .or_else(|| meta.map(|m| m.branch.clone()))
-            .unwrap_or_else(|| {
+            .or_else(|| meta.map(|m| m.branch.clone()));
+        let branch = match stored {
+            Some(branch) => branch,
+            None => {
                 warn!(
                     "No stored branch for repo '{}'; deriving branch name for pruning",
                     alias
                 );
-                build_branch_name(config, ticket_id, meta.and_then(|m| m.description.as_ref()))
-            });
+                build_branch_name(config, ticket_id, None, None, Some(repo_def))?
+            }
+        };
         let worktree_name = meta
             .and_then(|m| m.repo_worktrees.get(alias))
             .cloned()
@@ -158,19 +521,13 @@ fn prune_worktrees(
                 );
                 crate::core::ticket::worktree_name_for_branch(&branch)
             });
-
-        debug!(
-            "Pruning worktree metadata '{}' in repo {:?}",
-            worktree_name, repo_def.path
-        );
-        git::remove_worktree(&repo_def.path, &worktree_name).with_context(|| {
-            format!(
-                "Failed to prune worktree '{}' for repo '{}' at {:?}",
-                worktree_name, alias, repo_def.path
-            )
-        })?;
+        prunes.push(WorktreePrune {
+            alias: alias.clone(),
+            repo_path: repo_def.path.clone(),
+            worktree_name,
+        });
     }
-    Ok(())
+    Ok(prunes)
 }
 
 #[cfg(test)]
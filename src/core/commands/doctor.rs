@@ -1,16 +1,25 @@
-//! Validate configuration and environment for tix.
+//! Validate configuration, environment and ticket workspaces for tix.
 
+use crate::core::commands::refresh_files;
 use crate::core::config::{Config, RepoDefinition};
+use crate::core::git;
+use crate::core::ticket::{Ticket, metadata_path, worktree_name_for_branch};
 use log::{error, info, warn};
-use std::path::Path;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-/// Run a series of checks and report issues.
-pub fn run() -> anyhow::Result<()> {
-    let config = Config::load()?;
+/// Run a series of checks and report issues. With `fix`, repair the safe workspace problems.
+pub fn run(fix: bool) -> anyhow::Result<()> {
+    let config = Config::load_unvalidated()?;
 
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
+    if let Err(e) = config.validate() {
+        errors.push(format!("{:#}", e));
+    }
+
     check_string(
         "branch_prefix",
         &config.branch_prefix,
@@ -47,6 +56,29 @@ pub fn run() -> anyhow::Result<()> {
     for (alias, repo) in &config.repositories {
         check_repo(alias, repo, &mut warnings);
     }
+    check_groups(&config, &mut warnings);
+    check_templates(&config, &mut warnings);
+
+    let mut repaired = 0;
+    for finding in check_workspaces(&config) {
+        match (&finding.repair, fix) {
+            (Some(repair), true) => match apply_repair(&config, repair) {
+                Ok(()) => {
+                    info!("Fixed: {}", finding.message);
+                    repaired += 1;
+                }
+                Err(e) => warnings.push(format!("{} (fix failed: {:#})", finding.message, e)),
+            },
+            (Some(_), false) => warnings.push(format!(
+                "{} (repairable with 'tix doctor --fix')",
+                finding.message
+            )),
+            (None, _) => warnings.push(finding.message),
+        }
+    }
+    if fix {
+        info!("Repaired {} workspace problem(s).", repaired);
+    }
 
     for e in &errors {
         error!("{}", e);
@@ -63,6 +95,261 @@ pub fn run() -> anyhow::Result<()> {
     }
 }
 
+/// A workspace inconsistency found by the deep checks.
+struct Finding {
+    message: String,
+    /// The repair `--fix` applies; `None` when fixing it needs a human decision.
+    repair: Option<Repair>,
+}
+
+impl Finding {
+    fn new(message: String, repair: Option<Repair>) -> Self {
+        Finding { message, repair }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Repair {
+    /// Prune git metadata of a worktree whose directory is gone.
+    PruneWorktree { repo: PathBuf, name: String },
+    /// Point a worktree directory and its git metadata at each other again.
+    Relink {
+        repo: PathBuf,
+        name: String,
+        dir: PathBuf,
+    },
+    /// Recreate a missing worktree directory on its recorded (still existing) branch.
+    Recreate {
+        ticket_root: PathBuf,
+        alias: String,
+        name: String,
+        branch: String,
+    },
+    /// Drop a repo whose worktree and branch are both gone from the ticket metadata.
+    ForgetRepo { ticket_root: PathBuf, alias: String },
+}
+
+/// Walk every ticket and every registered repo and report inconsistencies between ticket
+/// metadata, worktree directories and git's worktree metadata.
+///
+/// Stale worktree metadata a ticket finding already accounts for (a worktree that will be
+/// recreated or relinked) is not reported again at the repo level.
+fn check_workspaces(config: &Config) -> Vec<Finding> {
+    let mut ticket_findings = Vec::new();
+    // Worktrees (repo path, metadata name) a ticket finding already accounts for.
+    let mut claimed = HashSet::new();
+    for ticket_root in ticket_dirs(&config.tickets_directory) {
+        check_ticket(config, &ticket_root, &mut claimed, &mut ticket_findings);
+    }
+
+    let mut findings = Vec::new();
+    let mut aliases: Vec<&String> = config.repositories.keys().collect();
+    aliases.sort();
+    for alias in aliases {
+        let repo = &config.repositories[alias].path;
+        if !repo.exists() {
+            continue;
+        }
+        let worktrees = match git::registered_worktrees(repo) {
+            Ok(worktrees) => worktrees,
+            Err(e) => {
+                findings.push(Finding::new(format!("Repo '{}': {:#}", alias, e), None));
+                continue;
+            }
+        };
+        for worktree in worktrees {
+            if worktree.path.exists() || claimed.contains(&(repo.clone(), worktree.name.clone())) {
+                continue;
+            }
+            findings.push(Finding::new(
+                format!(
+                    "Repo '{}' has stale worktree metadata '{}' (directory {:?} is gone)",
+                    alias, worktree.name, worktree.path
+                ),
+                Some(Repair::PruneWorktree {
+                    repo: repo.clone(),
+                    name: worktree.name,
+                }),
+            ));
+        }
+    }
+    findings.extend(ticket_findings);
+    findings
+}
+
+/// Ticket workspace directories under `tickets_directory` (skips `.archive` and other dot dirs).
+fn ticket_dirs(tickets_directory: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(tickets_directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && !path
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn check_ticket(
+    config: &Config,
+    ticket_root: &Path,
+    claimed: &mut HashSet<(PathBuf, String)>,
+    findings: &mut Vec<Finding>,
+) {
+    if !metadata_path(ticket_root).exists() {
+        findings.push(Finding::new(
+            format!("Ticket directory {:?} has no .tix/info.toml", ticket_root),
+            None,
+        ));
+        return;
+    }
+    let metadata = match Ticket::load(ticket_root) {
+        Ok(ticket) => ticket.metadata,
+        Err(e) => {
+            findings.push(Finding::new(format!("{:?}: {:#}", ticket_root, e), None));
+            return;
+        }
+    };
+    let id = &metadata.id;
+
+    let mut aliases: Vec<&String> = metadata.repo_branches.keys().collect();
+    aliases.sort();
+    for alias in aliases {
+        let branch = &metadata.repo_branches[alias];
+        let Some(repo_def) = config.repositories.get(alias) else {
+            findings.push(Finding::new(
+                format!("Ticket '{}': repo '{}' is no longer in config", id, alias),
+                None,
+            ));
+            continue;
+        };
+        let repo = &repo_def.path;
+        if !repo.exists() {
+            // Already reported by check_repo.
+            continue;
+        }
+        let name = metadata
+            .repo_worktrees
+            .get(alias)
+            .cloned()
+            .unwrap_or_else(|| worktree_name_for_branch(branch));
+        let dir = ticket_root.join(alias);
+        let branch_exists = match git::branch_head(repo, branch) {
+            Ok(head) => head.is_some(),
+            Err(e) => {
+                findings.push(Finding::new(
+                    format!("Ticket '{}': repo '{}': {:#}", id, alias, e),
+                    None,
+                ));
+                continue;
+            }
+        };
+
+        if !dir.exists() {
+            let finding = if branch_exists {
+                // Recreating prunes the stale metadata itself.
+                claimed.insert((repo.clone(), name.clone()));
+                Finding::new(
+                    format!(
+                        "Ticket '{}': worktree directory for '{}' is missing",
+                        id, alias
+                    ),
+                    Some(Repair::Recreate {
+                        ticket_root: ticket_root.to_path_buf(),
+                        alias: alias.clone(),
+                        name,
+                        branch: branch.clone(),
+                    }),
+                )
+            } else {
+                Finding::new(
+                    format!(
+                        "Ticket '{}': worktree for '{}' and its branch '{}' are gone",
+                        id, alias, branch
+                    ),
+                    Some(Repair::ForgetRepo {
+                        ticket_root: ticket_root.to_path_buf(),
+                        alias: alias.clone(),
+                    }),
+                )
+            };
+            findings.push(finding);
+            continue;
+        }
+
+        claimed.insert((repo.clone(), name.clone()));
+        let registered = git::registered_worktrees(repo)
+            .unwrap_or_default()
+            .into_iter()
+            .any(|worktree| worktree.name == name);
+        if !registered {
+            findings.push(Finding::new(
+                format!(
+                    "Ticket '{}': worktree for '{}' has no git worktree metadata '{}' in {:?}; \
+                     recreate it with 'tix remove' and 'tix add'",
+                    id, alias, name, repo
+                ),
+                None,
+            ));
+        } else if !git::worktree_linked(repo, &name, &dir) {
+            findings.push(Finding::new(
+                format!(
+                    "Ticket '{}': worktree for '{}' is not linked to its git metadata '{}'",
+                    id, alias, name
+                ),
+                Some(Repair::Relink {
+                    repo: repo.clone(),
+                    name,
+                    dir,
+                }),
+            ));
+        }
+        if !branch_exists {
+            findings.push(Finding::new(
+                format!(
+                    "Ticket '{}': branch '{}' recorded for '{}' no longer exists",
+                    id, branch, alias
+                ),
+                None,
+            ));
+        }
+    }
+}
+
+fn apply_repair(config: &Config, repair: &Repair) -> anyhow::Result<()> {
+    match repair {
+        Repair::PruneWorktree { repo, name } => git::remove_worktree(repo, name),
+        Repair::Relink { repo, name, dir } => git::relink_worktree(repo, name, dir),
+        Repair::Recreate {
+            ticket_root,
+            alias,
+            name,
+            branch,
+        } => {
+            let repo_def = &config.repositories[alias];
+            git::remove_worktree(&repo_def.path, name)?;
+            git::create_worktree(
+                &repo_def.path,
+                &ticket_root.join(alias),
+                branch,
+                None,
+                &repo_def.remote_options(),
+            )?;
+            let metadata = Ticket::load(ticket_root)?.metadata;
+            if let Err(e) = refresh_files::refresh_repo(config, ticket_root, &metadata, alias) {
+                warn!("[{}] Could not carry local files: {:#}", alias, e);
+            }
+            Ok(())
+        }
+        Repair::ForgetRepo { ticket_root, alias } => Ticket::remove_repo(ticket_root, alias),
+    }
+}
+
 fn check_string(field: &str, value: &str, errors: &mut Vec<String>, _warnings: &mut Vec<String>) {
     if value.trim().is_empty() {
         errors.push(format!("{} is not set", field));
@@ -99,11 +386,102 @@ fn check_repo(alias: &str, repo: &RepoDefinition, warnings: &mut Vec<String>) {
     }
 }
 
+fn check_groups(config: &Config, warnings: &mut Vec<String>) {
+    let mut names: Vec<&String> = config.groups.keys().collect();
+    names.sort();
+    for name in names {
+        let members = &config.groups[name];
+        if members.is_empty() {
+            warnings.push(format!("Group '{}' has no repos", name));
+        }
+        for alias in members {
+            if !config.repositories.contains_key(alias) {
+                warnings.push(format!(
+                    "Group '{}' references unknown repo alias '{}'",
+                    name, alias
+                ));
+            }
+        }
+    }
+}
+
+fn check_templates(config: &Config, warnings: &mut Vec<String>) {
+    let mut names: Vec<&String> = config.templates.keys().collect();
+    names.sort();
+    for name in names {
+        let template = &config.templates[name];
+        match config.expand_aliases(&template.repos) {
+            Ok(aliases) => {
+                for alias in aliases {
+                    if !config.repositories.contains_key(&alias) {
+                        warnings.push(format!(
+                            "Template '{}' references unknown repo alias '{}'",
+                            name, alias
+                        ));
+                    }
+                }
+            }
+            Err(e) => warnings.push(format!("Template '{}': {}", name, e)),
+        }
+        let mut based: Vec<&String> = template.base_branches.keys().collect();
+        based.sort();
+        for alias in based {
+            if !config.repositories.contains_key(alias) {
+                warnings.push(format!(
+                    "Template '{}' sets a base branch for unknown repo alias '{}'",
+                    name, alias
+                ));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{check_path, check_repo, check_string};
-    use crate::core::config::RepoDefinition;
+    use super::{
+        Repair, check_groups, check_path, check_repo, check_string, check_templates,
+        check_workspaces,
+    };
+    use crate::core::config::{Config, RepoDefinition, TicketTemplate};
+    use crate::core::ticket::Ticket;
     use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn check_workspaces_forgets_repos_whose_worktree_and_branch_are_gone() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        git2::Repository::init(&repo).unwrap();
+        let mut config = Config {
+            tickets_directory: temp.path().join("tickets"),
+            ..Default::default()
+        };
+        config.repositories.insert(
+            "api".into(),
+            RepoDefinition {
+                path: repo,
+                ..Default::default()
+            },
+        );
+        let ticket_root = config.tickets_directory.join("T-1");
+        let branches = [("api".to_string(), "feature/T-1".to_string())];
+        Ticket::create(&ticket_root, "T-1", None, "feature/T-1", &branches, None).unwrap();
+        std::fs::create_dir_all(config.tickets_directory.join(".archive")).unwrap();
+
+        let findings = check_workspaces(&config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "Ticket 'T-1': worktree for 'api' and its branch 'feature/T-1' are gone"
+        );
+        assert_eq!(
+            findings[0].repair,
+            Some(Repair::ForgetRepo {
+                ticket_root,
+                alias: "api".into()
+            })
+        );
+    }
 
     #[test]
     fn check_string_flags_empty() {
@@ -135,9 +513,60 @@ mod tests {
             &RepoDefinition {
                 url: "git@github.com:org/api.git".into(),
                 path: PathBuf::from("/nope/api"),
+                ..Default::default()
             },
             &mut warnings,
         );
         assert!(!warnings.is_empty());
     }
+
+    #[test]
+    fn check_groups_reports_unknown_aliases() {
+        let mut config = Config::default();
+        config
+            .repositories
+            .insert("api".into(), RepoDefinition::default());
+        config
+            .groups
+            .insert("backend".into(), vec!["api".into(), "worker".into()]);
+        let mut warnings = Vec::new();
+        check_groups(&config, &mut warnings);
+        assert_eq!(
+            warnings,
+            vec!["Group 'backend' references unknown repo alias 'worker'"]
+        );
+    }
+
+    #[test]
+    fn check_templates_reports_unknown_repos_and_groups() {
+        let mut config = Config::default();
+        config
+            .repositories
+            .insert("api".into(), RepoDefinition::default());
+        config.templates.insert(
+            "api-change".into(),
+            TicketTemplate {
+                repos: vec!["api".into(), "sdk".into()],
+                base_branches: [("docs".to_string(), "develop".to_string())].into(),
+                ..Default::default()
+            },
+        );
+        config.templates.insert(
+            "frontend".into(),
+            TicketTemplate {
+                repos: vec!["@web".into()],
+                ..Default::default()
+            },
+        );
+        let mut warnings = Vec::new();
+        check_templates(&config, &mut warnings);
+        assert_eq!(
+            warnings,
+            vec![
+                "Template 'api-change' references unknown repo alias 'sdk'",
+                "Template 'api-change' sets a base branch for unknown repo alias 'docs'",
+                "Template 'frontend': Unknown repository group 'web' (see 'tix groups list')",
+            ]
+        );
+    }
 }
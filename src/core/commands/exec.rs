@@ -0,0 +1,196 @@
+//! Exec command: run a shell command in every worktree of a ticket.
+
+use crate::core::commands::common::locate_ticket_root;
+use crate::core::config::Config;
+use crate::core::parallel;
+use crate::core::plugins::{repo_env, ticket_env};
+use crate::core::process::{relay_lines, shell_command};
+use crate::core::ticket::Ticket;
+use anyhow::{Context, Result, bail};
+use log::{error, info, warn};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+
+/// Outcome of running the command in one worktree.
+#[derive(Debug)]
+enum ExecResult {
+    Passed,
+    Failed(String),
+}
+
+/// Run the exec command.
+pub fn run(
+    ticket: Option<&str>,
+    repos: &[String],
+    run_parallel: bool,
+    command: &[String],
+) -> Result<()> {
+    if command.is_empty() {
+        bail!("No command given. Usage: tix exec -- <cmd>");
+    }
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+
+    let targets = select_targets(ticket.metadata.repo_branches.keys(), repos)?;
+    if targets.is_empty() {
+        info!("No repositories in this ticket.");
+        return Ok(());
+    }
+
+    let jobs = if run_parallel {
+        parallel::resolve_jobs(None, config.max_parallel_jobs)
+    } else {
+        1
+    };
+    let ticket_id = ticket.metadata.id.as_str();
+    let results = parallel::run_bounded(&targets, jobs, |alias| {
+        let worktree_path = ticket_root.join(alias);
+        run_in_worktree(alias, &worktree_path, &ticket_root, ticket_id, command)
+    });
+
+    let mut failed = Vec::new();
+    info!("Summary:");
+    for (alias, result) in targets.iter().zip(results) {
+        match result {
+            ExecResult::Passed => info!("  {:<16} ok", alias),
+            ExecResult::Failed(reason) => {
+                error!("  {:<16} FAILED ({})", alias, reason);
+                failed.push(alias.as_str());
+            }
+        }
+    }
+    if !failed.is_empty() {
+        bail!(
+            "Command failed in {} of {} repo(s): {}",
+            failed.len(),
+            targets.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Pick the aliases to run in, sorted. An explicit `--repos` list must name repos in the ticket.
+fn select_targets<'a>(
+    ticket_aliases: impl Iterator<Item = &'a String>,
+    requested: &[String],
+) -> Result<Vec<String>> {
+    let mut available: Vec<String> = ticket_aliases.cloned().collect();
+    available.sort();
+    if requested.is_empty() {
+        return Ok(available);
+    }
+
+    let mut selected = Vec::new();
+    for alias in requested {
+        if available.contains(alias) {
+            if !selected.contains(alias) {
+                selected.push(alias.clone());
+            }
+        } else {
+            warn!("Repo '{}' is not part of this ticket. Skipping.", alias);
+        }
+    }
+    if selected.is_empty() {
+        bail!("None of the requested repos are part of this ticket");
+    }
+    selected.sort();
+    Ok(selected)
+}
+
+/// Build the process for `command`: a single argument runs through the shell, several are
+/// executed directly.
+fn build_command(command: &[String]) -> Command {
+    if command.len() == 1 {
+        shell_command(&command[0])
+    } else {
+        let mut cmd = Command::new(&command[0]);
+        cmd.args(&command[1..]);
+        cmd
+    }
+}
+
+fn run_in_worktree(
+    alias: &str,
+    worktree_path: &Path,
+    ticket_root: &Path,
+    ticket_id: &str,
+    command: &[String],
+) -> ExecResult {
+    if !worktree_path.exists() {
+        warn!("[{}] Worktree missing at {:?}", alias, worktree_path);
+        return ExecResult::Failed("worktree missing".to_string());
+    }
+
+    let mut cmd = build_command(command);
+    cmd.current_dir(worktree_path)
+        .envs(ticket_env(ticket_root, ticket_id))
+        .envs(repo_env(alias, worktree_path))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    match spawn_prefixed(alias, &mut cmd) {
+        Ok(status) if status.success() => ExecResult::Passed,
+        Ok(status) => ExecResult::Failed(match status.code() {
+            Some(code) => format!("exit code {}", code),
+            None => status.to_string(),
+        }),
+        Err(e) => {
+            error!("[{}] {:#}", alias, e);
+            ExecResult::Failed("could not start command".to_string())
+        }
+    }
+}
+
+/// Spawn `cmd` and relay its stdout/stderr line by line, prefixed with `[alias]`.
+fn spawn_prefixed(alias: &str, cmd: &mut Command) -> Result<ExitStatus> {
+    let program = PathBuf::from(cmd.get_program());
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", program))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    thread::scope(|scope| {
+        if let Some(out) = stdout {
+            scope.spawn(|| relay_lines(out, |line| println!("[{}] {}", alias, line)));
+        }
+        if let Some(err) = stderr {
+            scope.spawn(|| relay_lines(err, |line| eprintln!("[{}] {}", alias, line)));
+        }
+    });
+
+    child.wait().context("Failed to wait for command")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_targets_filters_to_ticket_repos() {
+        let aliases = ["web".to_string(), "api".to_string()];
+        assert_eq!(
+            select_targets(aliases.iter(), &[]).unwrap(),
+            vec!["api", "web"]
+        );
+        assert_eq!(
+            select_targets(aliases.iter(), &["web".into(), "other".into()]).unwrap(),
+            vec!["web"]
+        );
+        assert!(select_targets(aliases.iter(), &["other".into()]).is_err());
+    }
+
+    #[test]
+    fn build_command_uses_shell_for_single_argument() {
+        let cmd = build_command(&["echo hi && true".to_string()]);
+        assert_ne!(cmd.get_program(), "echo hi && true");
+
+        let cmd = build_command(&["git".to_string(), "status".to_string()]);
+        assert_eq!(cmd.get_program(), "git");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["status"]);
+    }
+}
@@ -0,0 +1,299 @@
+//! Garbage-collect tickets whose work has landed.
+//!
+//! A ticket qualifies when every branch in `repo_branches` has been merged into its repo's
+//! default branch, deleted on the remote or already deleted locally, and every worktree is
+//! clean. Qualifying tickets are destroyed the same way `tix destroy` does it and their local
+//! branches are deleted, except branches deleted on the remote that still hold commits found on
+//! no base or remote branch; those are kept.
+
+use crate::core::commands::common::combine_failures;
+use crate::core::commands::destroy::{ensure_not_inside, remove_ticket};
+use crate::core::commands::list::collect_tickets;
+use crate::core::config::Config;
+use crate::core::git::{self, BranchRetirement};
+use crate::core::ticket::TicketMetadata;
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use dialoguer::Confirm;
+use log::{debug, error, info, warn};
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+/// A ticket that qualifies for collection, with the reason for each of its repos.
+struct Candidate {
+    root: PathBuf,
+    metadata: TicketMetadata,
+    age: Option<TimeDelta>,
+    reasons: Vec<(String, BranchRetirement)>,
+}
+
+/// Run the gc command.
+pub fn run(dry_run: bool, older_than: Option<TimeDelta>, yes: bool) -> Result<()> {
+    let config = Config::load()?;
+    let tickets = collect_tickets(&config)?;
+    if dry_run {
+        info!("Dry run: not fetching; using remote-tracking branches as of the last fetch");
+    } else {
+        fetch_repos(&config, &tickets);
+    }
+
+    let now = Utc::now();
+    let candidates: Vec<Candidate> = tickets
+        .into_iter()
+        .filter_map(|(root, metadata)| assess(&config, root, metadata, older_than, now))
+        .collect();
+    if candidates.is_empty() {
+        info!("No tickets to collect.");
+        return Ok(());
+    }
+    print_table(&candidates);
+
+    if dry_run {
+        info!("Dry run: {} ticket(s) would be destroyed", candidates.len());
+        return Ok(());
+    }
+    let interactive = io::stdin().is_terminal() && io::stderr().is_terminal();
+    if !yes && !interactive {
+        warn!("Not running in a terminal; re-run with --yes to destroy these tickets");
+        return Ok(());
+    }
+
+    let mut destroyed = 0;
+    let mut failures = Vec::new();
+    for candidate in &candidates {
+        let id = &candidate.metadata.id;
+        if !yes
+            && !Confirm::new()
+                .with_prompt(format!(
+                    "Destroy ticket '{}' and delete its local branches?",
+                    id
+                ))
+                .default(false)
+                .interact()?
+        {
+            continue;
+        }
+        match collect(&config, candidate) {
+            Ok(()) => destroyed += 1,
+            Err(e) => {
+                error!("[{}] {:#}", id, e);
+                failures.push(e.context(id.clone()));
+            }
+        }
+    }
+    info!("Destroyed {} of {} ticket(s)", destroyed, candidates.len());
+    combine_failures(failures, "Failed to collect one or more tickets")
+}
+
+/// Fetch every repo used by a ticket (pruning deleted remote branches) so that merges and
+/// remote deletions are visible. Failures only cost accuracy, so they are logged and skipped.
+fn fetch_repos(config: &Config, tickets: &[(PathBuf, TicketMetadata)]) {
+    let aliases: BTreeSet<&String> = tickets
+        .iter()
+        .flat_map(|(_, metadata)| metadata.repo_branches.keys())
+        .collect();
+    for alias in aliases {
+        let Some(repo_def) = config.repositories.get(alias) else {
+            continue;
+        };
+        if !repo_def.path.exists() {
+            continue;
+        }
+        info!("[{}] Fetching {:?}", alias, repo_def.path);
+        if let Err(e) = git::fetch_pruned(&repo_def.path, &repo_def.remote_options()) {
+            warn!(
+                "[{}] {:#}; using the remote-tracking branches as they are",
+                alias, e
+            );
+        }
+    }
+}
+
+/// Decide whether a ticket qualifies. Tickets that are kept are logged at debug level.
+fn assess(
+    config: &Config,
+    root: PathBuf,
+    metadata: TicketMetadata,
+    older_than: Option<TimeDelta>,
+    now: DateTime<Utc>,
+) -> Option<Candidate> {
+    let id = &metadata.id;
+    let age = ticket_age(&metadata, now);
+    if let Some(min) = older_than
+        && age.is_none_or(|age| age < min)
+    {
+        debug!("[{}] Not older than {}h; keeping", id, min.num_hours());
+        return None;
+    }
+    if metadata.repo_branches.is_empty() {
+        debug!("[{}] No repos; keeping", id);
+        return None;
+    }
+
+    let mut aliases: Vec<&String> = metadata.repo_branches.keys().collect();
+    aliases.sort();
+    let mut reasons = Vec::new();
+    for alias in aliases {
+        let Some(repo_def) = config.repositories.get(alias) else {
+            debug!("[{}/{}] Not registered in config; keeping", id, alias);
+            return None;
+        };
+        let worktree = root.join(alias);
+        if worktree.exists() {
+            match git::is_clean_ignoring(&worktree, metadata.carried_files(alias)) {
+                Ok(true) => {}
+                Ok(false) => {
+                    debug!(
+                        "[{}/{}] Worktree has uncommitted changes; keeping",
+                        id, alias
+                    );
+                    return None;
+                }
+                Err(e) => {
+                    warn!("[{}/{}] {:#}; keeping", id, alias, e);
+                    return None;
+                }
+            }
+        }
+
+        let branch = &metadata.repo_branches[alias];
+        let base = git::sync_base_ref(&repo_def.path, &metadata.remote_options(alias, repo_def));
+        match git::branch_retirement(&repo_def.path, branch, base.as_deref()) {
+            Ok(Some(reason)) => reasons.push((alias.clone(), reason)),
+            Ok(None) => {
+                debug!(
+                    "[{}/{}] Branch '{}' is still active; keeping",
+                    id, alias, branch
+                );
+                return None;
+            }
+            Err(e) => {
+                warn!("[{}/{}] {:#}; keeping", id, alias, e);
+                return None;
+            }
+        }
+    }
+
+    Some(Candidate {
+        root,
+        metadata,
+        age,
+        reasons,
+    })
+}
+
+/// Time since the ticket was created, if `created_at` parses.
+fn ticket_age(metadata: &TicketMetadata, now: DateTime<Utc>) -> Option<TimeDelta> {
+    let created = DateTime::parse_from_rfc3339(&metadata.created_at).ok()?;
+    Some(now.signed_duration_since(created.with_timezone(&Utc)))
+}
+
+/// Destroy one ticket and delete the local branches that still exist.
+fn collect(config: &Config, candidate: &Candidate) -> Result<()> {
+    let metadata = &candidate.metadata;
+    ensure_not_inside(&candidate.root)?;
+    remove_ticket(config, &metadata.id, &candidate.root, Some(metadata))?;
+
+    for (alias, reason) in &candidate.reasons {
+        if !deletes_branch(reason) {
+            continue;
+        }
+        let repo_def = &config.repositories[alias];
+        let branch = &metadata.repo_branches[alias];
+        git::delete_local_branch(&repo_def.path, branch)
+            .with_context(|| format!("Failed to delete branch '{}' in repo '{}'", branch, alias))?;
+        info!("[{}/{}] Deleted branch '{}'", metadata.id, alias, branch);
+    }
+    info!("Destroyed ticket '{}'", metadata.id);
+    Ok(())
+}
+
+fn print_table(candidates: &[Candidate]) {
+    println!("{:<20} {:<6} {:<20} REASON", "TICKET", "AGE", "REPO");
+    println!("{}", "-".repeat(90));
+    for candidate in candidates {
+        let age = candidate
+            .age
+            .map(|age| format!("{}d", age.num_days()))
+            .unwrap_or_else(|| "?".to_string());
+        for (index, (alias, reason)) in candidate.reasons.iter().enumerate() {
+            let (ticket, age) = if index == 0 {
+                (candidate.metadata.id.as_str(), age.as_str())
+            } else {
+                ("", "")
+            };
+            let branch = &candidate.metadata.repo_branches[alias];
+            println!(
+                "{:<20} {:<6} {:<20} {}",
+                ticket,
+                age,
+                alias,
+                describe(branch, reason)
+            );
+        }
+    }
+}
+
+/// Whether collecting the ticket deletes the local branch for `reason`.
+fn deletes_branch(reason: &BranchRetirement) -> bool {
+    match reason {
+        BranchRetirement::Deleted => false,
+        BranchRetirement::Merged(_) => true,
+        BranchRetirement::RemoteDeleted { unpushed, .. } => *unpushed == 0,
+    }
+}
+
+fn describe(branch: &str, reason: &BranchRetirement) -> String {
+    match reason {
+        BranchRetirement::Deleted => format!("branch '{}' already deleted", branch),
+        BranchRetirement::Merged(base) => format!("'{}' merged into {}", branch, base),
+        BranchRetirement::RemoteDeleted {
+            upstream,
+            unpushed: 0,
+        } => format!("'{}' deleted on the remote ({})", branch, upstream),
+        BranchRetirement::RemoteDeleted { upstream, unpushed } => format!(
+            "'{}' deleted on the remote ({}); branch kept: {} commit(s) not on any base or remote branch",
+            branch, upstream, unpushed
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branches_with_unpushed_commits_are_kept() {
+        let gone = |unpushed| BranchRetirement::RemoteDeleted {
+            upstream: "origin/feature/T-1".into(),
+            unpushed,
+        };
+        assert!(deletes_branch(&gone(0)));
+        assert!(!deletes_branch(&gone(2)));
+        assert!(!deletes_branch(&BranchRetirement::Deleted));
+        assert!(describe("feature/T-1", &gone(2)).contains("branch kept: 2 commit(s)"));
+    }
+
+    #[test]
+    fn ticket_age_is_measured_from_creation() {
+        let metadata = TicketMetadata {
+            id: "JIRA-1".into(),
+            created_at: "2024-01-01T00:00:00+02:00".into(),
+            ..Default::default()
+        };
+        let now = DateTime::parse_from_rfc3339("2024-01-31T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            ticket_age(&metadata, now),
+            Some(TimeDelta::days(30) + TimeDelta::hours(2))
+        );
+
+        let broken = TicketMetadata {
+            created_at: "yesterday".into(),
+            ..metadata
+        };
+        assert_eq!(ticket_age(&broken, now), None);
+    }
+}
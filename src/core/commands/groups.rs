@@ -0,0 +1,95 @@
+//! Repository group management commands.
+
+use crate::core::config::{Config, GROUP_PREFIX, validate_group_name};
+use anyhow::{Result, bail};
+use log::{info, warn};
+
+/// List configured groups.
+pub fn list() -> Result<()> {
+    let config = Config::load()?;
+    if config.groups.is_empty() {
+        info!("No groups configured.");
+        info!("Hint: Use 'tix groups add <name> <repo>...' to create one.");
+        return Ok(());
+    }
+
+    let mut names: Vec<&String> = config.groups.keys().collect();
+    names.sort();
+    for name in names {
+        info!(
+            "{}{}: {}",
+            GROUP_PREFIX,
+            name,
+            config.groups[name].join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Create a group, or add repositories to an existing one.
+pub fn add(name: &str, repos: &[String]) -> Result<()> {
+    let name = name.strip_prefix(GROUP_PREFIX).unwrap_or(name);
+    validate_group_name(name)?;
+    let mut config = Config::load()?;
+
+    let unknown: Vec<&str> = repos
+        .iter()
+        .filter(|alias| !config.repositories.contains_key(*alias))
+        .map(|alias| alias.as_str())
+        .collect();
+    if !unknown.is_empty() {
+        bail!(
+            "Unknown repository alias(es): {} (register them with 'tix add-repo')",
+            unknown.join(", ")
+        );
+    }
+
+    let created = !config.groups.contains_key(name);
+    let members = config.groups.entry(name.to_string()).or_default();
+    let mut added = 0;
+    for alias in repos {
+        if !members.contains(alias) {
+            members.push(alias.clone());
+            added += 1;
+        }
+    }
+    config.save()?;
+
+    if created {
+        info!("Created group '{}' with {} repo(s)", name, added);
+    } else {
+        info!("Added {} repo(s) to group '{}'", added, name);
+    }
+    Ok(())
+}
+
+/// Remove repositories from a group; with no repositories, delete the group.
+pub fn remove(name: &str, repos: &[String]) -> Result<()> {
+    let name = name.strip_prefix(GROUP_PREFIX).unwrap_or(name);
+    let mut config = Config::load()?;
+    let Some(members) = config.groups.get_mut(name) else {
+        bail!("Group '{}' does not exist", name);
+    };
+
+    if repos.is_empty() {
+        config.groups.remove(name);
+        config.save()?;
+        info!("Removed group '{}'", name);
+        return Ok(());
+    }
+
+    for alias in repos {
+        if !members.contains(alias) {
+            warn!("Repo '{}' is not in group '{}'. Skipping.", alias, name);
+        }
+    }
+    members.retain(|alias| !repos.contains(alias));
+    if members.is_empty() {
+        config.groups.remove(name);
+        info!("Removed group '{}' (no repos left)", name);
+    } else {
+        info!("Updated group '{}': {}", name, members.join(", "));
+    }
+    config.save()?;
+    Ok(())
+}
@@ -2,19 +2,84 @@
 
 use crate::core::commands::common::locate_ticket_root;
 use crate::core::config::Config;
-use crate::core::ticket::Ticket;
+use crate::core::output::{self, OutputFormat, TicketRecord};
+use crate::core::ticket::{Ticket, TicketMetadata};
+use crate::core::tracker;
 use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct TicketInfo {
+    ticket: TicketRecord,
+}
 
 /// Run the info command.
-pub fn run(ticket: Option<&str>) -> Result<()> {
+pub fn run(ticket: Option<&str>, format: OutputFormat) -> Result<()> {
     let config = Config::load()?;
     let ticket_root = locate_ticket_root(ticket, &config)?;
 
     let ticket_meta = Ticket::load(&ticket_root)?;
+    let metadata = &ticket_meta.metadata;
+    let record = TicketRecord::new(
+        metadata,
+        &ticket_root,
+        tracker::issue_link(&config, &metadata.id),
+    );
 
-    let description = ticket_meta.metadata.description.as_deref().unwrap_or("");
-
-    println!("[{}] {}", ticket_meta.metadata.id, description);
+    match format {
+        OutputFormat::Json => output::print_json(&TicketInfo { ticket: record }),
+        OutputFormat::Tsv => {
+            output::print_tsv(
+                &[
+                    "ticket",
+                    "description",
+                    "branch",
+                    "repos",
+                    "path",
+                    "link",
+                    "status",
+                ],
+                &[vec![
+                    record.id,
+                    record.description.unwrap_or_default(),
+                    record.branch,
+                    record.repos.join(","),
+                    record.path,
+                    record.link.clone().unwrap_or_default(),
+                    record.status.to_string(),
+                ]],
+            );
+            Ok(())
+        }
+        OutputFormat::Table => {
+            let description = metadata.description.as_deref().unwrap_or("");
+            println!("[{}] {}", metadata.id, description);
+            println!("Status: {}", format_status(metadata));
+            if let Some(issue) = &metadata.issue {
+                let mut details: Vec<&str> = Vec::new();
+                details.extend(issue.issue_type.as_deref());
+                details.extend(issue.assignee.as_deref());
+                println!(
+                    "Issue: {} [{}]",
+                    issue.status.as_deref().unwrap_or("unknown"),
+                    details.join(", ")
+                );
+            }
+            if let Some(link) = &record.link {
+                println!("Link: {}", link);
+            }
+            Ok(())
+        }
+    }
+}
 
-    Ok(())
+/// Format the lifecycle status with the time of the last transition, if known.
+pub fn format_status(metadata: &TicketMetadata) -> String {
+    let status = metadata.status.unwrap_or_default();
+    match metadata.status_history.last() {
+        Some(transition) if transition.to == status => {
+            format!("{} (since {})", status, transition.at)
+        }
+        _ => status.to_string(),
+    }
 }
@@ -9,10 +9,10 @@ use std::path::{Path, PathBuf};
 
 /// Expand a path string, handling a leading "~/" to the user's home directory.
 pub fn expand_path(input: &str) -> PathBuf {
-    if let Some(rest) = input.strip_prefix("~/") {
-        if let Some(home) = home::home_dir() {
-            return home.join(rest);
-        }
+    if let Some(rest) = input.strip_prefix("~/")
+        && let Some(home) = home::home_dir()
+    {
+        return home.join(rest);
     }
     PathBuf::from(input)
 }
@@ -0,0 +1,32 @@
+//! Issue tracker commands.
+
+use crate::core::commands::common::locate_ticket_root;
+use crate::core::config::Config;
+use crate::core::ticket::Ticket;
+use crate::core::tracker;
+use anyhow::{Context, Result};
+use log::info;
+
+/// Re-fetch the ticket's issue and update summary, type, assignee and status.
+pub fn refresh(ticket: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+    let id = &ticket.metadata.id;
+
+    let tracker = tracker::require(&config)?;
+    let issue = tracker.fetch_issue(id)?;
+    let previous = ticket.metadata.issue.as_ref();
+    let previous_status = previous.and_then(|i| i.status.as_deref());
+    match (previous_status, issue.status.as_deref()) {
+        (Some(old), Some(new)) if old != new => info!("{} status: {} -> {}", id, old, new),
+        (_, Some(new)) => info!("{} status: {}", id, new),
+        _ => {}
+    }
+    if previous.is_some_and(|p| p.summary != issue.summary) {
+        info!("{} summary: {}", id, issue.summary);
+    }
+    Ticket::set_issue(&ticket_root, issue)?;
+    info!("Refreshed {} from {}", id, tracker.name());
+    Ok(())
+}
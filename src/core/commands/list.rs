@@ -1,31 +1,90 @@
 //! List all ticket workspaces.
 
 use crate::core::config::Config;
-use crate::core::ticket::Ticket;
+use crate::core::output::{self, OutputFormat, TicketRecord};
+use crate::core::ticket::{Ticket, TicketMetadata, TicketStatus, archive_root};
+use crate::core::tracker::{self, IssueTracker};
 use anyhow::{Context, Result};
 use log::warn;
+use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Run the list command.
-pub fn run() -> Result<()> {
+pub fn run(archived: bool, status: Option<TicketStatus>, format: OutputFormat) -> Result<()> {
     let config = Config::load()?;
+    let mut tickets = if archived {
+        collect_tickets_in(&archive_root(&config.tickets_directory))?
+    } else {
+        collect_tickets(&config)?
+    };
+    if let Some(wanted) = status {
+        tickets.retain(|(_, metadata)| metadata.status.unwrap_or_default() == wanted);
+    }
+
+    let tracker = tracker::from_config(&config);
+    let tracker = tracker.as_deref();
+    match format {
+        OutputFormat::Json => {
+            let records: Vec<TicketRecord> = tickets
+                .iter()
+                .map(|(path, metadata)| {
+                    TicketRecord::new(metadata, path, issue_link(tracker, &metadata.id))
+                })
+                .collect();
+            output::print_json(&TicketList { tickets: records })
+        }
+        OutputFormat::Tsv => {
+            let rows: Vec<Vec<String>> = tickets
+                .iter()
+                .map(|(path, metadata)| {
+                    vec![
+                        metadata.id.clone(),
+                        metadata.description.clone().unwrap_or_default(),
+                        path.display().to_string(),
+                        format_link(tracker, &metadata.id),
+                        metadata.status.unwrap_or_default().to_string(),
+                    ]
+                })
+                .collect();
+            output::print_tsv(&["ticket", "description", "path", "link", "status"], &rows);
+            Ok(())
+        }
+        OutputFormat::Table => {
+            print_table(tracker, tickets);
+            Ok(())
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TicketList {
+    tickets: Vec<TicketRecord>,
+}
 
+/// Load every ticket workspace under `tickets_directory`, sorted by ticket ID.
+pub fn collect_tickets(config: &Config) -> Result<Vec<(PathBuf, TicketMetadata)>> {
     // Check if tickets directory exists
     if !config.tickets_directory.exists() {
         warn!(
             "Tickets directory does not exist: {:?}",
             config.tickets_directory
         );
-        println!("No tickets found.");
-        return Ok(());
+        return Ok(Vec::new());
+    }
+    collect_tickets_in(&config.tickets_directory)
+}
+
+/// Load every ticket workspace directly under `dir`, sorted by ticket ID.
+pub fn collect_tickets_in(dir: &Path) -> Result<Vec<(PathBuf, TicketMetadata)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
     }
 
     // Collect all ticket directories
     let mut tickets = Vec::new();
 
-    let entries =
-        fs::read_dir(&config.tickets_directory).context("Failed to read tickets directory")?;
+    let entries = fs::read_dir(dir).context("Failed to read tickets directory")?;
 
     for entry in entries {
         let entry = entry?;
@@ -45,38 +104,40 @@ pub fn run() -> Result<()> {
         }
     }
 
+    // Sort by ticket ID
+    tickets.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+    Ok(tickets)
+}
+
+fn print_table(tracker: Option<&dyn IssueTracker>, tickets: Vec<(PathBuf, TicketMetadata)>) {
     if tickets.is_empty() {
         println!("No tickets found.");
-        return Ok(());
+        return;
     }
 
-    // Sort by ticket ID
-    tickets.sort_by(|a, b| a.1.id.cmp(&b.1.id));
-
     // Display table header
     println!(
-        "{:<20} {:<40} {:<40} {}",
-        "TICKET", "DESCRIPTION", "PATH", "JIRA LINK"
+        "{:<20} {:<12} {:<40} {:<40} LINK",
+        "TICKET", "STATUS", "DESCRIPTION", "PATH"
     );
-    println!("{}", "-".repeat(140));
+    println!("{}", "-".repeat(153));
 
     // Display each ticket
     for (path, metadata) in tickets {
         let ticket_id = &metadata.id;
         let description = metadata.description.as_deref().unwrap_or("");
         let display_path = format_path_with_home(&path);
-        let jira_link = format_jira_link(&config, ticket_id);
+        let link = format_link(tracker, ticket_id);
 
         println!(
-            "{:<20} {:<40} {:<40} {}",
+            "{:<20} {:<12} {:<40} {:<40} {}",
             ticket_id,
+            metadata.status.unwrap_or_default().to_string(),
             truncate(description, 40),
             truncate(&display_path, 40),
-            jira_link
+            link
         );
     }
-
-    Ok(())
 }
 
 /// Replace the home directory prefix with ~ for display.
@@ -89,15 +150,14 @@ fn format_path_with_home(path: &Path) -> String {
     path.display().to_string()
 }
 
-/// Format a Jira link if jira_base_url is configured.
-fn format_jira_link(config: &Config, ticket_id: &str) -> String {
-    match &config.jira_base_url {
-        Some(base_url) => {
-            let base = base_url.trim_end_matches('/');
-            format!("{}/{}", base, ticket_id)
-        }
-        None => String::new(),
-    }
+/// Format the issue link for the link column (empty without a tracker or for foreign ids).
+fn format_link(tracker: Option<&dyn IssueTracker>, ticket_id: &str) -> String {
+    issue_link(tracker, ticket_id).unwrap_or_default()
+}
+
+/// Build the tracker link for a ticket, or `None` when no tracker is configured.
+fn issue_link(tracker: Option<&dyn IssueTracker>, ticket_id: &str) -> Option<String> {
+    tracker?.issue_url(ticket_id)
 }
 
 /// Truncate a string to a maximum length, adding "..." if truncated.
@@ -120,7 +180,7 @@ fn truncate(s: &str, max_len: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use crate::core::config::{TrackerConfig, TrackerKind};
 
     #[test]
     fn truncate_leaves_short_strings() {
@@ -150,39 +210,62 @@ mod tests {
         assert_eq!(truncate("hello", 1), "h");
     }
 
+    fn link(config: &Config, ticket_id: &str) -> String {
+        format_link(tracker::from_config(config).as_deref(), ticket_id)
+    }
+
     #[test]
-    fn format_jira_link_returns_empty_when_not_configured() {
+    fn format_link_returns_empty_when_not_configured() {
         let config = Config {
             jira_base_url: None,
             ..Default::default()
         };
-        assert_eq!(format_jira_link(&config, "JIRA-123"), "");
+        assert_eq!(link(&config, "JIRA-123"), "");
     }
 
     #[test]
-    fn format_jira_link_constructs_url_when_configured() {
+    fn format_link_constructs_jira_url_when_configured() {
         let config = Config {
             jira_base_url: Some("https://company.atlassian.net/browse".to_string()),
             ..Default::default()
         };
         assert_eq!(
-            format_jira_link(&config, "JIRA-123"),
+            link(&config, "JIRA-123"),
             "https://company.atlassian.net/browse/JIRA-123"
         );
     }
 
     #[test]
-    fn format_jira_link_handles_trailing_slash() {
+    fn format_link_handles_trailing_slash() {
         let config = Config {
             jira_base_url: Some("https://company.atlassian.net/browse/".to_string()),
             ..Default::default()
         };
         assert_eq!(
-            format_jira_link(&config, "JIRA-123"),
+            link(&config, "JIRA-123"),
             "https://company.atlassian.net/browse/JIRA-123"
         );
     }
 
+    #[test]
+    fn format_link_uses_github_tracker() {
+        let config = Config {
+            github_base_url: "https://github.com".to_string(),
+            jira_base_url: Some("https://company.atlassian.net/browse".to_string()),
+            tracker: Some(TrackerConfig {
+                kind: TrackerKind::GitHub,
+                repository: Some("my-org/web".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            link(&config, "GH-12"),
+            "https://github.com/my-org/web/issues/12"
+        );
+        assert_eq!(link(&config, "JIRA-x"), "");
+    }
+
     #[test]
     fn format_path_with_home_uses_tilde() {
         if let Some(home) = home::home_dir() {
@@ -1,14 +1,26 @@
 pub mod add;
 pub mod add_repo;
+pub mod archive;
 pub mod common;
 pub mod config_cmd;
 pub mod destroy;
 pub mod doctor;
+pub mod exec;
+pub mod gc;
+pub mod groups;
 pub mod info;
 pub mod init;
+pub mod issue;
 pub mod list;
 pub mod plugins;
+pub mod pr;
+pub mod push;
+pub mod refresh_files;
 pub mod remove;
+pub mod repair;
+pub mod restore;
 pub mod setup;
 pub mod setup_repos;
+pub mod status;
+pub mod sync;
 pub mod update;
@@ -130,7 +130,6 @@ mod tests {
     use super::register;
     use crate::core::config::Config;
     use std::fs;
-    use std::path::PathBuf;
     use std::sync::Mutex;
 
     static ENV_LOCK: Mutex<()> = Mutex::new(());
@@ -158,10 +157,7 @@ mod tests {
         let config = Config::load().unwrap();
         let plugin = config.plugins.get("my-plugin").unwrap();
         assert!(plugin.entrypoint.is_absolute());
-        assert_eq!(
-            plugin.entrypoint,
-            PathBuf::from(entrypoint.canonicalize().unwrap())
-        );
+        assert_eq!(plugin.entrypoint, entrypoint.canonicalize().unwrap());
 
         std::env::set_current_dir(original_cwd).unwrap();
         unsafe {
@@ -0,0 +1,551 @@
+//! Pull request commands: open one GitHub pull request per ticket branch and report on them.
+
+use crate::core::commands::common::{combine_failures, locate_ticket_root};
+use crate::core::config::{Config, RepoDefinition};
+use crate::core::git::{self, RemoteOptions};
+use crate::core::github::{self, GitHubClient};
+use crate::core::output::{self, OutputFormat, TicketRecord};
+use crate::core::ticket::{PullRequestRef, Ticket, TicketMetadata};
+use crate::core::tracker;
+use anyhow::{Context, Result, anyhow};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Marks the start of the cross-link section tix maintains in pull request bodies.
+const RELATED_MARKER: &str = "<!-- tix:related-pull-requests -->";
+
+#[derive(Deserialize)]
+struct PullRequest {
+    number: u64,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    head: Option<PullRequestHead>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestHead {
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct Review {
+    #[serde(default)]
+    user: Option<ReviewUser>,
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct ReviewUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct CombinedStatus {
+    state: String,
+    #[serde(default)]
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+struct CheckRuns {
+    #[serde(default)]
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Deserialize)]
+struct CheckRun {
+    status: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+}
+
+/// Open a pull request for every repo branch in the ticket, then cross-link them.
+///
+/// Repos that already have a recorded pull request are left alone, and an open pull request
+/// GitHub already has for the branch is adopted instead of creating a duplicate.
+pub fn create(ticket: Option<&str>, draft: bool) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+    let metadata = &ticket.metadata;
+
+    let mut aliases: Vec<&String> = metadata.repo_branches.keys().collect();
+    aliases.sort();
+    if aliases.is_empty() {
+        info!("No repositories in this ticket.");
+        return Ok(());
+    }
+
+    let client = GitHubClient::from_config(&config)?;
+    let link = tracker::issue_link(&config, &metadata.id);
+    let mut pull_requests = metadata.pull_requests.clone();
+    let mut created = false;
+    let mut failures = Vec::new();
+    for alias in aliases {
+        if let Some(existing) = pull_requests.get(alias) {
+            info!(
+                "[{}] Pull request already recorded: {}",
+                alias, existing.url
+            );
+            continue;
+        }
+        let Some(repo_def) = config.repositories.get(alias) else {
+            warn!("[{}] Not registered in config; skipping", alias);
+            continue;
+        };
+        let branch = &metadata.repo_branches[alias];
+        let remote = metadata.remote_options(alias, repo_def);
+        match open_pull_request(
+            &client,
+            metadata,
+            link.as_deref(),
+            repo_def,
+            &remote,
+            branch,
+            draft,
+        ) {
+            Ok(pull_request) => {
+                info!("[{}] {}", alias, pull_request.url);
+                Ticket::set_pull_request(&ticket_root, alias, pull_request.clone())?;
+                pull_requests.insert(alias.clone(), pull_request);
+                created = true;
+            }
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e.context(format!("Failed to open a pull request for '{}'", alias)));
+            }
+        }
+    }
+
+    if created && pull_requests.len() > 1 {
+        for (alias, pull_request) in sorted(&pull_requests) {
+            if let Err(e) = link_siblings(&client, alias, pull_request, &pull_requests) {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e);
+            }
+        }
+    }
+    combine_failures(failures, "Failed to open one or more pull requests")
+}
+
+fn open_pull_request(
+    client: &GitHubClient,
+    metadata: &TicketMetadata,
+    link: Option<&str>,
+    repo_def: &RepoDefinition,
+    remote: &RemoteOptions,
+    branch: &str,
+    draft: bool,
+) -> Result<PullRequestRef> {
+    let repository = github::repo_slug(&repo_def.url).ok_or_else(|| {
+        anyhow!(
+            "Cannot tell the GitHub repository from url '{}'",
+            repo_def.url
+        )
+    })?;
+    let owner = repository.split('/').next().unwrap_or_default();
+
+    let open: Vec<PullRequest> = client.get_with_query(
+        &format!("/repos/{}/pulls", repository),
+        &[
+            ("head", &format!("{}:{}", owner, branch)),
+            ("state", "open"),
+        ],
+    )?;
+    let pull_request = match open.into_iter().next() {
+        Some(existing) => {
+            info!(
+                "Found open pull request #{} for '{}'",
+                existing.number, branch
+            );
+            existing
+        }
+        None => {
+            let base = base_branch(repo_def, remote).with_context(|| {
+                format!("Could not determine the base branch in {:?}", repo_def.path)
+            })?;
+            let title = match &metadata.description {
+                Some(description) => format!("{}: {}", metadata.id, description),
+                None => metadata.id.clone(),
+            };
+            client.post(
+                &format!("/repos/{}/pulls", repository),
+                &json!({
+                    "title": title,
+                    "head": branch,
+                    "base": base,
+                    "body": pull_request_body(metadata, link),
+                    "draft": draft,
+                }),
+            )?
+        }
+    };
+    Ok(PullRequestRef {
+        repository,
+        number: pull_request.number,
+        url: pull_request.html_url,
+    })
+}
+
+/// Short name of the branch pull requests target: the ticket's base branch for the repo, else
+/// the remote's default.
+fn base_branch(repo_def: &RepoDefinition, remote: &RemoteOptions) -> Option<String> {
+    if let Some(base) = remote.base_branch {
+        return Some(base.to_string());
+    }
+    let name = git::default_branch_for(&repo_def.path, remote)?;
+    let short = git::short_ref_name(&name);
+    let remote_prefix = format!("{}/", remote.remote);
+    Some(
+        short
+            .strip_prefix(&remote_prefix)
+            .filter(|_| name.starts_with("refs/remotes/"))
+            .unwrap_or(short)
+            .to_string(),
+    )
+}
+
+fn pull_request_body(metadata: &TicketMetadata, link: Option<&str>) -> String {
+    let mut body = match link {
+        Some(link) => format!("Ticket: [{}]({})", metadata.id, link),
+        None => format!("Ticket: {}", metadata.id),
+    };
+    if let Some(description) = &metadata.description {
+        body = format!("{}\n\n{}", description, body);
+    }
+    body
+}
+
+/// Rewrite the related pull requests section of `pull_request`'s body, keeping the rest.
+fn link_siblings(
+    client: &GitHubClient,
+    alias: &str,
+    pull_request: &PullRequestRef,
+    all: &HashMap<String, PullRequestRef>,
+) -> Result<()> {
+    let path = format!(
+        "/repos/{}/pulls/{}",
+        pull_request.repository, pull_request.number
+    );
+    let current: PullRequest = client.get(&path)?;
+    let siblings: Vec<String> = sorted(all)
+        .into_iter()
+        .filter(|(other, _)| *other != alias)
+        .map(|(other, pr)| format!("- {}: {}", other, pr.url))
+        .collect();
+    let body = with_related_section(current.body.as_deref().unwrap_or(""), &siblings);
+    let _: PullRequest = client
+        .patch(&path, &json!({ "body": body }))
+        .with_context(|| {
+            format!(
+                "Failed to link sibling pull requests on {}",
+                pull_request.url
+            )
+        })?;
+    info!(
+        "[{}] Linked {} sibling pull request(s)",
+        alias,
+        siblings.len()
+    );
+    Ok(())
+}
+
+fn with_related_section(body: &str, siblings: &[String]) -> String {
+    let kept = body
+        .split_once(RELATED_MARKER)
+        .map_or(body, |(before, _)| before)
+        .trim_end();
+    let section = format!(
+        "{}\nRelated pull requests:\n{}",
+        RELATED_MARKER,
+        siblings.join("\n")
+    );
+    if kept.is_empty() {
+        section
+    } else {
+        format!("{}\n\n{}", kept, section)
+    }
+}
+
+fn sorted(pull_requests: &HashMap<String, PullRequestRef>) -> Vec<(&String, &PullRequestRef)> {
+    let mut entries: Vec<_> = pull_requests.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+#[derive(Serialize)]
+struct PullRequestStatus {
+    repo: String,
+    repository: String,
+    number: u64,
+    url: String,
+    /// `open`, `draft`, `merged` or `closed`; `unknown` when GitHub could not be reached.
+    state: String,
+    /// `approved`, `changes requested` or `pending`.
+    review: String,
+    /// `success`, `failure`, `pending` or `none`.
+    ci: String,
+}
+
+#[derive(Serialize)]
+struct PullRequestDocument {
+    ticket: TicketRecord,
+    pull_requests: Vec<PullRequestStatus>,
+}
+
+/// Show review and CI state for the ticket's recorded pull requests.
+pub fn status(ticket: Option<&str>, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+
+    let mut statuses = Vec::new();
+    let mut failures = Vec::new();
+    if !ticket.metadata.pull_requests.is_empty() {
+        let client = GitHubClient::from_config(&config)?;
+        for (alias, pull_request) in sorted(&ticket.metadata.pull_requests) {
+            let mut record = PullRequestStatus {
+                repo: alias.clone(),
+                repository: pull_request.repository.clone(),
+                number: pull_request.number,
+                url: pull_request.url.clone(),
+                state: "unknown".into(),
+                review: "unknown".into(),
+                ci: "unknown".into(),
+            };
+            if let Err(e) = fetch_status(&client, pull_request, &mut record) {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e);
+            }
+            statuses.push(record);
+        }
+    }
+
+    match format {
+        OutputFormat::Json => {
+            output::print_json(&PullRequestDocument {
+                ticket: TicketRecord::new(
+                    &ticket.metadata,
+                    &ticket_root,
+                    tracker::issue_link(&config, &ticket.metadata.id),
+                ),
+                pull_requests: statuses,
+            })?;
+        }
+        OutputFormat::Tsv => {
+            let rows: Vec<Vec<String>> = statuses
+                .iter()
+                .map(|s| {
+                    vec![
+                        s.repo.clone(),
+                        s.repository.clone(),
+                        s.number.to_string(),
+                        s.state.clone(),
+                        s.review.clone(),
+                        s.ci.clone(),
+                        s.url.clone(),
+                    ]
+                })
+                .collect();
+            output::print_tsv(
+                &[
+                    "repo",
+                    "repository",
+                    "number",
+                    "state",
+                    "review",
+                    "ci",
+                    "url",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Table => {
+            if statuses.is_empty() {
+                println!("No pull requests recorded. Run 'tix pr create' first.");
+            } else {
+                println!(
+                    "{:<16} {:>6} {:<8} {:<18} {:<8} URL",
+                    "REPO", "PR", "STATE", "REVIEW", "CI"
+                );
+                println!("{}", "-".repeat(80));
+                for s in &statuses {
+                    println!(
+                        "{:<16} {:>6} {:<8} {:<18} {:<8} {}",
+                        s.repo,
+                        format!("#{}", s.number),
+                        s.state,
+                        s.review,
+                        s.ci,
+                        s.url
+                    );
+                }
+            }
+        }
+    }
+    combine_failures(failures, "Failed to fetch one or more pull requests")
+}
+
+fn fetch_status(
+    client: &GitHubClient,
+    pull_request: &PullRequestRef,
+    record: &mut PullRequestStatus,
+) -> Result<()> {
+    let repo_path = format!("/repos/{}", pull_request.repository);
+    let pr_path = format!("{}/pulls/{}", repo_path, pull_request.number);
+    let current: PullRequest = client
+        .get(&pr_path)
+        .with_context(|| format!("Failed to fetch {}", pull_request.url))?;
+    record.state = pull_request_state(&current).to_string();
+
+    // One page of 100 is plenty for a ticket's pull request; GitHub's default is 30.
+    let reviews: Vec<Review> =
+        client.get_with_query(&format!("{}/reviews", pr_path), &[("per_page", "100")])?;
+    record.review = review_state(&reviews).to_string();
+
+    if let Some(head) = &current.head {
+        let combined: CombinedStatus =
+            client.get(&format!("{}/commits/{}/status", repo_path, head.sha))?;
+        let checks: CheckRuns = client.get_with_query(
+            &format!("{}/commits/{}/check-runs", repo_path, head.sha),
+            &[("per_page", "100")],
+        )?;
+        record.ci = ci_state(&combined, &checks.check_runs).to_string();
+    }
+    Ok(())
+}
+
+fn pull_request_state(pull_request: &PullRequest) -> &'static str {
+    if pull_request.merged {
+        "merged"
+    } else if pull_request.state == "closed" {
+        "closed"
+    } else if pull_request.draft {
+        "draft"
+    } else {
+        "open"
+    }
+}
+
+/// Summarise reviews using each reviewer's latest decisive review (comments are ignored).
+fn review_state(reviews: &[Review]) -> &'static str {
+    let mut latest: HashMap<&str, &str> = HashMap::new();
+    for review in reviews {
+        let user = review.user.as_ref().map_or("", |u| u.login.as_str());
+        match review.state.as_str() {
+            "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED" => {
+                latest.insert(user, review.state.as_str());
+            }
+            _ => {}
+        }
+    }
+    if latest.values().any(|s| *s == "CHANGES_REQUESTED") {
+        "changes requested"
+    } else if latest.values().any(|s| *s == "APPROVED") {
+        "approved"
+    } else {
+        "pending"
+    }
+}
+
+/// Combine commit statuses and check runs into a single CI state.
+fn ci_state(combined: &CombinedStatus, check_runs: &[CheckRun]) -> &'static str {
+    let check_failed = check_runs.iter().any(|run| {
+        matches!(
+            run.conclusion.as_deref(),
+            Some("failure" | "cancelled" | "timed_out" | "action_required" | "startup_failure")
+        )
+    });
+    let has_statuses = combined.total_count > 0;
+    if check_failed || (has_statuses && matches!(combined.state.as_str(), "failure" | "error")) {
+        "failure"
+    } else if check_runs.iter().any(|run| run.status != "completed")
+        || (has_statuses && combined.state == "pending")
+    {
+        "pending"
+    } else if has_statuses || !check_runs.is_empty() {
+        "success"
+    } else {
+        "none"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(user: &str, state: &str) -> Review {
+        Review {
+            user: Some(ReviewUser { login: user.into() }),
+            state: state.into(),
+        }
+    }
+
+    #[test]
+    fn review_state_uses_latest_review_per_user() {
+        assert_eq!(review_state(&[]), "pending");
+        assert_eq!(
+            review_state(&[
+                review("ann", "CHANGES_REQUESTED"),
+                review("ann", "COMMENTED"),
+                review("ann", "APPROVED"),
+            ]),
+            "approved"
+        );
+        assert_eq!(
+            review_state(&[
+                review("ann", "APPROVED"),
+                review("bob", "CHANGES_REQUESTED")
+            ]),
+            "changes requested"
+        );
+    }
+
+    #[test]
+    fn ci_state_combines_statuses_and_check_runs() {
+        let none = CombinedStatus {
+            state: "pending".into(),
+            total_count: 0,
+        };
+        let run = |status: &str, conclusion: Option<&str>| CheckRun {
+            status: status.into(),
+            conclusion: conclusion.map(str::to_string),
+        };
+        assert_eq!(ci_state(&none, &[]), "none");
+        assert_eq!(
+            ci_state(&none, &[run("completed", Some("success"))]),
+            "success"
+        );
+        assert_eq!(
+            ci_state(
+                &none,
+                &[run("completed", Some("success")), run("in_progress", None)]
+            ),
+            "pending"
+        );
+        let failed = CombinedStatus {
+            state: "failure".into(),
+            total_count: 1,
+        };
+        assert_eq!(ci_state(&failed, &[run("in_progress", None)]), "failure");
+    }
+
+    #[test]
+    fn related_section_is_replaced_not_appended() {
+        let siblings = vec!["- web: https://github.com/o/web/pull/2".to_string()];
+        let first = with_related_section("Fix login\n\nTicket: ABC-1", &siblings);
+        assert!(first.starts_with("Fix login\n\nTicket: ABC-1\n\n"));
+        let second = with_related_section(&first, &siblings);
+        assert_eq!(first, second);
+    }
+}
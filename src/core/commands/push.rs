@@ -0,0 +1,78 @@
+//! Push command: publish every ticket branch and set its upstream.
+
+use crate::core::commands::common::{combine_failures, locate_ticket_root};
+use crate::core::config::{Config, RepoDefinition};
+use crate::core::git::{self, PushOutcome, RemoteOptions};
+use crate::core::ticket::Ticket;
+use anyhow::{Context, Result};
+use log::{error, info, warn};
+
+/// Run the push command.
+pub fn run(ticket: Option<&str>, force_with_lease: bool) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+
+    let mut aliases: Vec<&String> = ticket.metadata.repo_branches.keys().collect();
+    aliases.sort();
+    if aliases.is_empty() {
+        info!("No repositories in this ticket.");
+        return Ok(());
+    }
+
+    let mut summary = Vec::new();
+    let mut failures = Vec::new();
+    for alias in aliases {
+        let branch = &ticket.metadata.repo_branches[alias];
+        let Some(repo_def) = config.repositories.get(alias) else {
+            warn!("[{}] Not registered in config; skipping", alias);
+            summary.push((alias.as_str(), branch.as_str(), "skipped (not in config)"));
+            continue;
+        };
+
+        let remote = ticket.metadata.remote_options(alias, repo_def);
+        match push_repo(alias, repo_def, &remote, branch, force_with_lease) {
+            Ok(outcome) => summary.push((alias.as_str(), branch.as_str(), describe(outcome))),
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                summary.push((alias.as_str(), branch.as_str(), "failed"));
+                failures.push(e);
+            }
+        }
+    }
+
+    info!("Push summary for {}:", ticket.metadata.id);
+    for (alias, branch, result) in &summary {
+        info!("  {:<16} {:<36} {}", alias, branch, result);
+    }
+    combine_failures(failures, "Failed to push one or more repositories")
+}
+
+fn push_repo(
+    alias: &str,
+    repo_def: &RepoDefinition,
+    remote: &RemoteOptions,
+    branch: &str,
+    force_with_lease: bool,
+) -> Result<PushOutcome> {
+    let base = git::default_branch_for(&repo_def.path, remote);
+    info!("[{}] Pushing '{}'", alias, branch);
+    git::push_branch(
+        &repo_def.path,
+        repo_def.remote_name(),
+        branch,
+        base.as_deref(),
+        force_with_lease,
+    )
+    .with_context(|| format!("Failed to push '{}' in repo '{}'", branch, alias))
+}
+
+fn describe(outcome: PushOutcome) -> &'static str {
+    match outcome {
+        PushOutcome::UpToDate => "up to date",
+        PushOutcome::NoCommits => "nothing to push",
+        PushOutcome::Created => "pushed (new branch)",
+        PushOutcome::Updated => "pushed",
+        PushOutcome::Forced => "force-pushed (with lease)",
+    }
+}
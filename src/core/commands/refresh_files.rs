@@ -0,0 +1,72 @@
+//! Refresh-files command: carry `carry_files` into a ticket's worktrees again.
+
+use crate::core::carry;
+use crate::core::commands::common::{combine_failures, locate_ticket_root};
+use crate::core::config::Config;
+use crate::core::ticket::{Ticket, TicketMetadata};
+use anyhow::{Context, Result, bail};
+use log::{debug, error, info};
+use std::path::Path;
+
+/// Run the refresh-files command.
+pub fn run(ticket: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+
+    let mut aliases: Vec<&String> = ticket.metadata.repo_branches.keys().collect();
+    aliases.sort();
+
+    let mut refreshed = 0;
+    let mut failures = Vec::new();
+    for alias in aliases {
+        match refresh_repo(&config, &ticket_root, &ticket.metadata, alias) {
+            Ok(Some(_)) => refreshed += 1,
+            Ok(None) => debug!("[{}] No carry_files configured", alias),
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e);
+            }
+        }
+    }
+    combine_failures(
+        failures,
+        "Failed to refresh files in one or more repositories",
+    )?;
+
+    if refreshed == 0 {
+        info!("No carry_files configured for the repos in this ticket.");
+    }
+    Ok(())
+}
+
+/// Carry files into the worktree for `alias` using the patterns recorded in the ticket, or the
+/// repo's `carry_files` when nothing was recorded. Returns the number of files carried, or
+/// `None` when there is nothing to carry.
+pub fn refresh_repo(
+    config: &Config,
+    ticket_root: &Path,
+    metadata: &TicketMetadata,
+    alias: &str,
+) -> Result<Option<usize>> {
+    let Some(repo_def) = config.repositories.get(alias) else {
+        bail!("Repo alias '{}' is not registered in config", alias);
+    };
+    let (patterns, mode) = match metadata.repo_carried_files.get(alias) {
+        Some(recorded) => (recorded.patterns.clone(), recorded.mode),
+        None => (repo_def.carry_files.clone(), repo_def.carry_mode),
+    };
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let worktree = ticket_root.join(alias);
+    if !worktree.exists() {
+        bail!("Worktree missing at {:?}", worktree);
+    }
+
+    let carried = carry::carry(&repo_def.path, &worktree, &patterns, mode)?;
+    let count = carried.files.len();
+    Ticket::set_carried_files(ticket_root, alias, carried)?;
+    info!("[{}] Carried {} file(s)", alias, count);
+    Ok(Some(count))
+}
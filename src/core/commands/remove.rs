@@ -1,19 +1,85 @@
 //! Remove a repo worktree from an existing ticket with safety checks.
 
-use crate::core::commands::common::{build_branch_name, locate_ticket_root};
-use crate::core::config::Config;
+use crate::core::carry;
+use crate::core::commands::common::{
+    branch_name_for_metadata, combine_failures, ensure_branches_published, locate_ticket_root,
+};
+use crate::core::config::{Config, RepoDefinition};
 use crate::core::git;
-use crate::core::ticket::Ticket;
+use crate::core::hooks::{self, HookEvent, HookTarget};
+use crate::core::plan::{self, Action};
+use crate::core::ticket::{Ticket, metadata_path};
 use anyhow::{Context, Result, anyhow, bail};
-use log::{info, warn};
+use log::{error, info, warn};
 use std::fs;
+use std::path::{Path, PathBuf};
 
-/// Run the remove command.
-pub fn run(repo_alias: &str, ticket: Option<&str>) -> Result<()> {
+/// Run the remove command. `repos` may contain `@group` references. With `dry_run`, only print
+/// the plan.
+pub fn run(
+    repos: &[String],
+    ticket: Option<&str>,
+    allow_unpushed: bool,
+    dry_run: bool,
+) -> Result<()> {
     let config = Config::load()?;
     let ticket_root = locate_ticket_root(ticket, &config)?;
 
-    let ticket_meta = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+    let aliases = config.expand_aliases(repos)?;
+    if dry_run {
+        let mut actions = Vec::new();
+        for alias in &aliases {
+            let plan = plan_remove(&config, &ticket_root, alias, allow_unpushed)
+                .with_context(|| format!("Failed to remove '{}'", alias))?;
+            actions.extend(describe(&config, &ticket_root, &plan));
+        }
+        plan::print(&actions);
+        return Ok(());
+    }
+    if let [alias] = aliases.as_slice() {
+        return remove_repo(&config, &ticket_root, alias, allow_unpushed);
+    }
+
+    let mut failures = Vec::new();
+    for alias in &aliases {
+        if let Err(e) = remove_repo(&config, &ticket_root, alias, allow_unpushed)
+            .with_context(|| format!("Failed to remove '{}'", alias))
+        {
+            error!("{:#}", e);
+            failures.push(e);
+        }
+    }
+    combine_failures(failures, "Failed to remove one or more repositories")
+}
+
+/// A repo worktree that passed the safety checks and can be removed.
+struct RemovePlan<'a> {
+    alias: &'a str,
+    repo_def: &'a RepoDefinition,
+    ticket_meta: Ticket,
+    target_worktree: PathBuf,
+    worktree_name: String,
+}
+
+/// Remove the worktree for a single alias.
+fn remove_repo(
+    config: &Config,
+    ticket_root: &Path,
+    repo_alias: &str,
+    allow_unpushed: bool,
+) -> Result<()> {
+    let plan = plan_remove(config, ticket_root, repo_alias, allow_unpushed)?;
+    apply(config, ticket_root, &plan)
+}
+
+/// Run the safety checks for removing `repo_alias` and resolve its worktree registration.
+fn plan_remove<'a>(
+    config: &'a Config,
+    ticket_root: &Path,
+    repo_alias: &'a str,
+    allow_unpushed: bool,
+) -> Result<RemovePlan<'a>> {
+    let ticket_meta = Ticket::load(ticket_root).context("Failed to load ticket metadata")?;
 
     let repo_def = config
         .repositories
@@ -30,7 +96,8 @@ pub fn run(repo_alias: &str, ticket: Option<&str>) -> Result<()> {
     }
 
     // Safety: ensure worktree is clean
-    let is_clean = git::is_clean(&target_worktree).with_context(|| {
+    let carried = ticket_meta.metadata.carried_files(repo_alias);
+    let is_clean = git::is_clean_ignoring(&target_worktree, carried).with_context(|| {
         format!(
             "Could not check clean status for worktree {:?}",
             target_worktree
@@ -43,25 +110,20 @@ pub fn run(repo_alias: &str, ticket: Option<&str>) -> Result<()> {
         );
     }
 
-    info!(
-        "Removing worktree for '{}' at {:?}",
-        repo_alias, target_worktree
-    );
-    fs::remove_dir_all(&target_worktree)
-        .with_context(|| format!("Failed to remove {:?}", target_worktree))?;
+    // Safety: refuse to orphan commits that were never pushed or merged
+    if !allow_unpushed {
+        ensure_branches_published(
+            config,
+            &ticket_meta.metadata,
+            &[repo_alias.to_string()],
+            "remove",
+        )?;
+    }
 
-    let branch_for_repo = ticket_meta
-        .metadata
-        .repo_branches
-        .get(repo_alias)
-        .cloned()
-        .unwrap_or_else(|| {
-            build_branch_name(
-                &config,
-                &ticket_meta.metadata.id,
-                ticket_meta.metadata.description.as_ref(),
-            )
-        });
+    let branch_for_repo = match ticket_meta.metadata.repo_branches.get(repo_alias) {
+        Some(branch) => branch.clone(),
+        None => branch_name_for_metadata(config, &ticket_meta.metadata, Some(repo_def))?,
+    };
     let worktree_name = ticket_meta
         .metadata
         .repo_worktrees
@@ -75,10 +137,60 @@ pub fn run(repo_alias: &str, ticket: Option<&str>) -> Result<()> {
             crate::core::ticket::worktree_name_for_branch(&branch_for_repo)
         });
 
-    git::remove_worktree(&repo_def.path, &worktree_name).with_context(|| {
+    Ok(RemovePlan {
+        alias: repo_alias,
+        repo_def,
+        ticket_meta,
+        target_worktree,
+        worktree_name,
+    })
+}
+
+/// Actions `apply` would take for `plan`.
+fn describe(config: &Config, ticket_root: &Path, plan: &RemovePlan) -> Vec<Action> {
+    let mut actions = plan::hook_actions(
+        HookEvent::PreRemove,
+        config,
+        Some(plan.repo_def),
+        plan.alias,
+    );
+    actions.push(Action::RemoveDir(plan.target_worktree.clone()));
+    actions.push(Action::PruneWorktree {
+        alias: plan.alias.to_string(),
+        name: plan.worktree_name.clone(),
+    });
+    actions.push(Action::WriteMetadata(metadata_path(ticket_root)));
+    actions
+}
+
+fn apply(config: &Config, ticket_root: &Path, plan: &RemovePlan) -> Result<()> {
+    let repo_alias = plan.alias;
+    let repo_def = plan.repo_def;
+    let target_worktree = &plan.target_worktree;
+    let ticket_meta = &plan.ticket_meta;
+    let carried = ticket_meta.metadata.carried_files(repo_alias);
+
+    let target = HookTarget {
+        alias: repo_alias,
+        worktree: target_worktree,
+        ticket_root,
+        ticket_id: &ticket_meta.metadata.id,
+    };
+    hooks::run(HookEvent::PreRemove, config, Some(repo_def), &target)?;
+
+    info!(
+        "Removing worktree for '{}' at {:?}",
+        repo_alias, target_worktree
+    );
+    // Carried symlinks point into the main checkout; unlink them so deletion never follows them.
+    carry::unlink_symlinks(target_worktree, carried)?;
+    fs::remove_dir_all(target_worktree)
+        .with_context(|| format!("Failed to remove {:?}", target_worktree))?;
+
+    git::remove_worktree(&repo_def.path, &plan.worktree_name).with_context(|| {
         format!(
             "Failed to prune worktree metadata '{}' for repo '{}'",
-            worktree_name, repo_alias
+            plan.worktree_name, repo_alias
         )
     })?;
 
@@ -86,7 +198,7 @@ pub fn run(repo_alias: &str, ticket: Option<&str>) -> Result<()> {
         "Removed worktree '{}' from ticket '{}'",
         repo_alias, ticket_meta.metadata.id
     );
-    Ticket::remove_repo(&ticket_root, repo_alias)
+    Ticket::remove_repo(ticket_root, repo_alias)
         .with_context(|| format!("Failed to update ticket metadata for '{}'", repo_alias))?;
     Ok(())
 }
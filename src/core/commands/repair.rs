@@ -0,0 +1,122 @@
+//! Repair command: re-link ticket worktrees after the tickets or code directory moved.
+//!
+//! A linked worktree and its repository point at each other by absolute path (the worktree's
+//! `.git` file and the repository's `.git/worktrees/<name>/gitdir`), so moving either side
+//! breaks both. `tix repair` rewrites both links from the ticket metadata and the configured
+//! repository paths.
+
+use crate::core::commands::common::combine_failures;
+use crate::core::commands::list::collect_tickets;
+use crate::core::config::Config;
+use crate::core::git;
+use crate::core::ticket::{Ticket, TicketMetadata, worktree_name_for_branch};
+use crate::core::ticket_id;
+use anyhow::{Context, Result, bail};
+use log::{debug, error, info, warn};
+use std::path::Path;
+
+/// Run the repair command for one ticket, or for every ticket when `ticket` is `None`.
+pub fn run(ticket: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    match ticket {
+        Some(id) => {
+            let ticket_root = ticket_id::ticket_dir(&config, id)?;
+            let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+            let (relinked, failures) = repair_ticket(&config, &ticket_root, &ticket.metadata);
+            info!(
+                "Re-linked {} worktree(s) in {}",
+                relinked, ticket.metadata.id
+            );
+            combine_failures(failures, "Failed to repair one or more worktrees")
+        }
+        None => repair_all(&config),
+    }
+}
+
+/// Re-link the worktrees of every ticket under `tickets_directory`.
+pub fn repair_all(config: &Config) -> Result<()> {
+    let tickets = collect_tickets(config)?;
+    if tickets.is_empty() {
+        info!("No tickets to repair.");
+        return Ok(());
+    }
+    let mut total = 0;
+    let mut failures = Vec::new();
+    for (ticket_root, metadata) in &tickets {
+        let (relinked, mut ticket_failures) = repair_ticket(config, ticket_root, metadata);
+        total += relinked;
+        failures.append(&mut ticket_failures);
+    }
+    info!(
+        "Re-linked {} worktree(s) across {} ticket(s)",
+        total,
+        tickets.len()
+    );
+    combine_failures(failures, "Failed to repair one or more worktrees")
+}
+
+/// Re-link each repo worktree of one ticket. Returns how many links were rewritten and the
+/// per-repo failures.
+fn repair_ticket(
+    config: &Config,
+    ticket_root: &Path,
+    metadata: &TicketMetadata,
+) -> (usize, Vec<anyhow::Error>) {
+    let mut aliases: Vec<&String> = metadata.repo_branches.keys().collect();
+    aliases.sort();
+
+    let mut relinked = 0;
+    let mut failures = Vec::new();
+    for alias in aliases {
+        let Some(repo_def) = config.repositories.get(alias) else {
+            warn!(
+                "[{}/{}] Not registered in config; skipping",
+                metadata.id, alias
+            );
+            continue;
+        };
+        let worktree = ticket_root.join(alias);
+        if !worktree.exists() {
+            warn!(
+                "[{}/{}] Worktree directory {:?} is missing; run 'tix doctor --fix' to recreate it",
+                metadata.id, alias, worktree
+            );
+            continue;
+        }
+        let name = metadata
+            .repo_worktrees
+            .get(alias)
+            .cloned()
+            .unwrap_or_else(|| worktree_name_for_branch(&metadata.repo_branches[alias]));
+        match relink(&repo_def.path, &name, &worktree) {
+            Ok(true) => {
+                info!(
+                    "[{}/{}] Re-linked {:?} with {:?}",
+                    metadata.id, alias, worktree, repo_def.path
+                );
+                relinked += 1;
+            }
+            Ok(false) => debug!("[{}/{}] Worktree is already linked", metadata.id, alias),
+            Err(e) => {
+                error!("[{}/{}] {:#}", metadata.id, alias, e);
+                failures.push(e.context(format!("{}/{}", metadata.id, alias)));
+            }
+        }
+    }
+    (relinked, failures)
+}
+
+/// Rewrite both sides of the link unless they already point at each other.
+fn relink(repo: &Path, name: &str, worktree: &Path) -> Result<bool> {
+    if !repo.exists() {
+        bail!(
+            "Repository {:?} does not exist; update its path in config first",
+            repo
+        );
+    }
+    if git::worktree_linked(repo, name, worktree) {
+        return Ok(false);
+    }
+    git::relink_worktree(repo, name, worktree)?;
+    Ok(true)
+}
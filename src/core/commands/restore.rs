@@ -0,0 +1,121 @@
+//! Restore an archived ticket by recreating its worktrees.
+//!
+//! The archive record stays in the ticket's `.tix/` until every worktree is back, so a restore
+//! that fails part-way can be finished by running `tix restore` again.
+
+use crate::core::commands::common::combine_failures;
+use crate::core::commands::refresh_files;
+use crate::core::config::Config;
+use crate::core::git;
+use crate::core::ticket::{ArchiveRecord, ArchivedRepo, Ticket, archive_root};
+use crate::core::ticket_id;
+use anyhow::{Context, Result, bail};
+use log::{debug, error, info, warn};
+use std::fs;
+use std::path::Path;
+
+/// Run the restore command.
+pub fn run(ticket_id: &str) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_id = &ticket_id::normalize(&config, ticket_id)?;
+    let ticket_dir = config.tickets_directory.join(ticket_id);
+    let archive_dir = archive_root(&config.tickets_directory).join(ticket_id);
+
+    if ticket_dir.exists() && ArchiveRecord::exists(&ticket_dir) {
+        info!("Resuming the restore of '{}'", ticket_id);
+    } else {
+        if !archive_dir.exists() {
+            bail!(
+                "No archived ticket '{}' found in {:?}",
+                ticket_id,
+                archive_dir
+            );
+        }
+        if ticket_dir.exists() {
+            bail!(
+                "Ticket directory {:?} already exists; refusing to overwrite it",
+                ticket_dir
+            );
+        }
+        fs::rename(&archive_dir, &ticket_dir).with_context(|| {
+            format!(
+                "Failed to move archived ticket {:?} to {:?}",
+                archive_dir, ticket_dir
+            )
+        })?;
+    }
+    let record = ArchiveRecord::load(&ticket_dir)?;
+
+    let metadata = Ticket::load(&ticket_dir).ok().map(|t| t.metadata);
+    let mut failures = Vec::new();
+    for (alias, repo) in &record.repos {
+        if ticket_dir.join(alias).exists() {
+            debug!("[{}] Worktree already restored", alias);
+            continue;
+        }
+        if let Err(e) = restore_repo(&config, &ticket_dir, alias, repo) {
+            error!("[{}] {:#}", alias, e);
+            failures.push(e);
+            continue;
+        }
+        if let Some(metadata) = &metadata
+            && let Err(e) = refresh_files::refresh_repo(&config, &ticket_dir, metadata, alias)
+        {
+            warn!("[{}] Could not carry local files: {:#}", alias, e);
+        }
+    }
+    if !failures.is_empty() {
+        info!(
+            "Hint: Fix the problem and run 'tix restore {}' again to finish restoring.",
+            ticket_id
+        );
+    }
+    combine_failures(failures, "Failed to restore one or more repositories")?;
+
+    ArchiveRecord::remove(&ticket_dir)?;
+    info!("Restored ticket '{}' at {:?}", ticket_id, ticket_dir);
+    Ok(())
+}
+
+fn restore_repo(
+    config: &Config,
+    ticket_dir: &Path,
+    alias: &str,
+    repo: &ArchivedRepo,
+) -> Result<()> {
+    let Some(repo_def) = config.repositories.get(alias) else {
+        bail!("Repo alias '{}' is no longer registered in config", alias);
+    };
+
+    match &repo.head {
+        Some(head) => {
+            if git::restore_branch(&repo_def.path, &repo.branch, head)? {
+                warn!(
+                    "[{}] Branch '{}' was deleted; recreated it at {}",
+                    alias, repo.branch, head
+                );
+            }
+        }
+        None => warn!(
+            "[{}] No head commit recorded; using branch '{}' as it is now",
+            alias, repo.branch
+        ),
+    }
+
+    let target = ticket_dir.join(alias);
+    git::create_worktree(
+        &repo_def.path,
+        &target,
+        &repo.branch,
+        None,
+        &repo_def.remote_options(),
+    )
+    .with_context(|| {
+        format!(
+            "Failed to recreate worktree for '{}' at {:?}",
+            alias, target
+        )
+    })?;
+    info!("[{}] Restored worktree on '{}'", alias, repo.branch);
+    Ok(())
+}
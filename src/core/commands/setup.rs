@@ -1,32 +1,117 @@
 //! Setup command: initialize a ticket workspace and create repo worktrees.
 
-use crate::core::commands::common::build_branch_name;
-use crate::core::config::Config;
+use crate::core::carry;
+use crate::core::commands::common::{build_branch_name, combine_failures};
+use crate::core::config::{Config, RepoDefinition, TicketTemplate};
 use crate::core::git;
-use crate::core::ticket::Ticket;
+use crate::core::hooks::{self, HookEvent, HookTarget};
+use crate::core::parallel;
+use crate::core::plan::{self, Action};
+use crate::core::ticket::{IssueDetails, Ticket, metadata_path};
+use crate::core::{ticket_id, tracker};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use log::{debug, error, info, warn};
 use std::fs;
+use std::path::{Path, PathBuf};
 
-pub fn run(
-    ticket_id: &str,
-    repos: &[String],
-    all: bool,
-    description: Option<String>,
-) -> Result<()> {
-    let config = Config::load()?;
+/// Command-line options for `tix setup`.
+#[derive(Debug, Default)]
+pub struct SetupOptions<'a> {
+    /// Repo aliases or `@group` names to include.
+    pub repos: &'a [String],
+    /// Include every registered repository.
+    pub all: bool,
+    /// Ticket description (falls back to the template's `description`).
+    pub description: Option<String>,
+    /// Keep completed steps when a later one fails.
+    pub keep_partial: bool,
+    /// Maximum repositories processed in parallel (falls back to `max_parallel_jobs`).
+    pub jobs: Option<usize>,
+    /// Selects a `branch_templates` entry and fills `{type}`.
+    pub ticket_type: Option<&'a str>,
+    /// Name of a `[templates.<name>]` entry to apply.
+    pub template: Option<&'a str>,
+    /// Fill the description and branch prefix from the tracker issue with the ticket's id.
+    pub from_tracker: bool,
+    /// Print the plan instead of applying it.
+    pub dry_run: bool,
+}
+
+pub fn run(ticket_id: &str, options: SetupOptions) -> Result<()> {
+    let mut config = Config::load()?;
+    let ticket_id = &ticket_id::normalize(&config, ticket_id)?;
+    if let Some(existing) = ticket_id::find_case_variant(&config.tickets_directory, ticket_id) {
+        bail!(
+            "Ticket '{}' would duplicate existing ticket '{}' (ids differ only by case)",
+            ticket_id,
+            existing
+        );
+    }
+    let template = match options.template {
+        Some(name) => Some((name, config.template(name)?.clone())),
+        None => None,
+    };
+    if let Some((name, template)) = &template {
+        info!("Using ticket template '{}'", name);
+        if let Some(prefix) = &template.branch_prefix {
+            config.branch_prefix = prefix.clone();
+        }
+    }
+    // A dry run only plans the fetch, so the planned branch ignores the issue's title and type.
+    let mut issue_tracker = None;
+    let issue = if options.from_tracker && options.dry_run {
+        let tracker = tracker::require(&config)?;
+        tracker.validate_id(ticket_id)?;
+        issue_tracker = Some(tracker.name());
+        None
+    } else if options.from_tracker {
+        let tracker = tracker::require(&config)?;
+        let issue = tracker.fetch_issue(ticket_id)?;
+        info!(
+            "Fetched {} from {}: {}",
+            ticket_id,
+            tracker.name(),
+            issue.summary
+        );
+        if let Some(prefix) = issue
+            .issue_type
+            .as_deref()
+            .and_then(|t| tracker.branch_prefix_for(t))
+        {
+            config.branch_prefix = prefix.to_string();
+        }
+        Some(issue)
+    } else {
+        None
+    };
+    let jobs = parallel::resolve_jobs(options.jobs, config.max_parallel_jobs);
     let ticket_dir = config.tickets_directory.join(ticket_id);
+    let description = options
+        .description
+        .or_else(|| {
+            issue
+                .as_ref()
+                .map(|i| i.summary.clone())
+                .filter(|s| !s.is_empty())
+        })
+        .or_else(|| template.as_ref().and_then(|(_, t)| t.description.clone()));
+    let ticket_type = options.ticket_type;
 
     // 1. Determine Target Repositories
-    let target_repos: Vec<String> = if all {
+    let mut requested: Vec<String> = template
+        .as_ref()
+        .map(|(_, t)| t.repos.clone())
+        .unwrap_or_default();
+    requested.extend(options.repos.iter().cloned());
+    let target_repos: Vec<String> = if options.all {
         debug!("Flag --all detected. Selecting all registered repositories.");
         config.repositories.keys().cloned().collect()
-    } else if !repos.is_empty() {
+    } else if !requested.is_empty() {
         let mut valid = Vec::new();
-        for alias in repos {
-            if config.repositories.contains_key(alias) {
-                valid.push(alias.clone());
+        for alias in config.expand_aliases(&requested)? {
+            if config.repositories.contains_key(&alias) {
+                valid.push(alias);
             } else {
                 warn!("Alias '{}' is not registered in config. Skipping.", alias);
             }
@@ -39,95 +124,452 @@ pub fn run(
     } else {
         // no repos specified, not --all
         warn!("No repositories specified.");
-        info!("Created empty ticket environment.");
-        info!("Hint: Use 'tix add <repo>' to add worktrees later.");
+        if !options.dry_run {
+            info!("Created empty ticket environment.");
+            info!("Hint: Use 'tix add <repo>' to add worktrees later.");
+        }
         Vec::new()
     };
 
     // 2. Compute branch name
-    let branch_name = build_branch_name(&config, ticket_id, description.as_ref());
+    if let Some(t) = ticket_type
+        && !config.branch_templates.contains_key(t)
+    {
+        debug!(
+            "No branch_templates entry for type '{}'; using branch_template",
+            t
+        );
+    }
+    let branch_name =
+        build_branch_name(&config, ticket_id, description.as_ref(), ticket_type, None)?;
+    // Repos with their own `branch_prefix` get their own branch name.
+    let mut repo_branches = Vec::new();
+    for alias in &target_repos {
+        let repo_def = config.repositories.get(alias);
+        let branch = match repo_def {
+            Some(def) if def.branch_prefix.is_some() => build_branch_name(
+                &config,
+                ticket_id,
+                description.as_ref(),
+                ticket_type,
+                Some(def),
+            )?,
+            _ => branch_name.clone(),
+        };
+        repo_branches.push((alias.clone(), branch));
+    }
+
+    let request = SetupRequest {
+        ticket_id,
+        ticket_dir,
+        repo_branches,
+        branch_name,
+        description: description.as_ref(),
+        ticket_type,
+        template: template.as_ref().map(|(name, t)| (*name, t)),
+        issue: issue.as_ref(),
+        issue_tracker,
+        jobs,
+    };
+    if options.dry_run {
+        plan::print(&describe(&config, &request)?);
+        return Ok(());
+    }
+    let mut tx = SetupTransaction::default();
+    let result = apply(&config, &request, &mut tx);
+
+    if let Err(e) = result {
+        if options.keep_partial {
+            warn!(
+                "Setup failed; keeping {} completed step(s) because --keep-partial was set",
+                tx.steps.len()
+            );
+            info!("Hint: Use 'tix add <repo>' to retry the remaining repositories.");
+        } else {
+            error!("Setup failed; rolling back {} step(s)", tx.steps.len());
+            tx.rollback();
+        }
+        return Err(e);
+    }
+
+    info!("Setup for {} complete!", ticket_id);
+    Ok(())
+}
+
+/// Resolved inputs for creating a ticket workspace.
+struct SetupRequest<'a> {
+    ticket_id: &'a str,
+    ticket_dir: PathBuf,
+    /// Target repo aliases paired with the branch each one uses.
+    repo_branches: Vec<(String, String)>,
+    branch_name: String,
+    description: Option<&'a String>,
+    ticket_type: Option<&'a str>,
+    /// Ticket template name and definition, when `--template` was given.
+    template: Option<(&'a str, &'a TicketTemplate)>,
+    /// Issue details fetched with `--from-tracker`.
+    issue: Option<&'a IssueDetails>,
+    /// Tracker a dry run would fetch the issue from.
+    issue_tracker: Option<&'static str>,
+    jobs: usize,
+}
+
+/// Actions `apply` would take for `request`.
+fn describe(config: &Config, request: &SetupRequest) -> Result<Vec<Action>> {
+    let ticket_dir = request.ticket_dir.as_path();
+    let mut actions = Vec::new();
+    if let Some(tracker) = request.issue_tracker {
+        actions.push(Action::FetchIssue {
+            ticket: request.ticket_id.to_string(),
+            tracker: tracker.to_string(),
+        });
+    }
+    if !ticket_dir.exists() {
+        actions.push(Action::CreateDir(ticket_dir.to_path_buf()));
+    }
+    actions.push(Action::WriteMetadata(metadata_path(ticket_dir)));
+
+    let base_branches = request.template.map(|(_, t)| &t.base_branches);
+    for (alias, branch) in &request.repo_branches {
+        let Some(repo_def) = config.repositories.get(alias) else {
+            continue;
+        };
+        let mut remote = repo_def.remote_options();
+        if let Some(base) = base_branches.and_then(|b| b.get(alias)) {
+            remote.base_branch = Some(base);
+        }
+        actions.push(Action::Fetch {
+            alias: alias.clone(),
+            repo: repo_def.path.clone(),
+        });
+        if let Some(from) = git::new_branch_start_point(&repo_def.path, branch, None, &remote)
+            .with_context(|| format!("Failed to inspect repository '{}'", alias))?
+        {
+            actions.push(Action::CreateBranch {
+                alias: alias.clone(),
+                branch: branch.clone(),
+                from,
+            });
+        }
+        actions.push(Action::CreateWorktree {
+            alias: alias.clone(),
+            path: ticket_dir.join(alias),
+            branch: branch.clone(),
+        });
+        actions.extend(plan::hook_actions(
+            HookEvent::PostSetup,
+            config,
+            Some(repo_def),
+            alias,
+        ));
+    }
+
+    if let Some((_, template)) = request.template {
+        for command in &template.post_create {
+            actions.push(Action::RunCommand {
+                command: command.clone(),
+                dir: ticket_dir.to_path_buf(),
+            });
+        }
+    }
+    Ok(actions)
+}
+
+fn apply(config: &Config, request: &SetupRequest, tx: &mut SetupTransaction) -> Result<()> {
+    let ticket_dir = request.ticket_dir.as_path();
+    let repo_branches = &request.repo_branches;
+    let branch_name = request.branch_name.as_str();
+    let jobs = request.jobs;
 
     // 3. Create or load the ticket directory and metadata
     if !ticket_dir.exists() {
         info!("Creating ticket directory at {:?}", ticket_dir);
-        fs::create_dir_all(&ticket_dir)?;
-
-        let repo_branches: Vec<(String, String)> = target_repos
-            .iter()
-            .map(|r| (r.clone(), branch_name.clone()))
-            .collect();
-        Ticket::create(
-            &ticket_dir,
-            ticket_id,
-            description.as_ref(),
-            &branch_name,
-            &repo_branches,
-        )?;
+        fs::create_dir_all(ticket_dir)?;
+        tx.record(SetupStep::CreatedTicketDir(ticket_dir.to_path_buf()));
     } else {
         debug!("Using existing ticket directory {:?}", ticket_dir);
+    }
 
-        // Check for metadata
-        match Ticket::load(&ticket_dir) {
-            Ok(existing) => {
-                if existing.metadata.branch.is_empty() {
-                    Ticket::ensure_branch(&ticket_dir, &branch_name)?;
-                }
-                Ticket::add_repos_with_branch(&ticket_dir, &target_repos, &branch_name)?;
+    let metadata_file = metadata_path(ticket_dir);
+    tx.record(SetupStep::WroteMetadata {
+        root: ticket_dir.to_path_buf(),
+        previous: fs::read_to_string(&metadata_file).ok(),
+        created_stamp_dir: !metadata_file.parent().is_some_and(Path::exists),
+    });
+    // Check for metadata. Repos are only recorded once their worktree exists.
+    match Ticket::load(ticket_dir) {
+        Ok(existing) => {
+            if existing.metadata.branch.is_empty() {
+                Ticket::ensure_branch(ticket_dir, branch_name)?;
             }
-            Err(e) => {
+        }
+        Err(e) => {
+            if tx.created_ticket_dir() {
+                debug!("Stamping new ticket metadata");
+            } else {
                 warn!("Missing .tix metadata in existing directory: {}", e);
                 info!("Initializing new .tix stamp");
-                let repo_branches: Vec<(String, String)> = target_repos
-                    .iter()
-                    .map(|r| (r.clone(), branch_name.clone()))
-                    .collect();
-                Ticket::create(
-                    &ticket_dir,
-                    ticket_id,
-                    description.as_ref(),
-                    &branch_name,
-                    &repo_branches,
-                )?;
             }
+            Ticket::create(
+                ticket_dir,
+                request.ticket_id,
+                request.description,
+                branch_name,
+                &[],
+                request.template.map(|(name, _)| name),
+            )?;
         }
     }
+    if let Some(ticket_type) = request.ticket_type {
+        Ticket::set_ticket_type(ticket_dir, ticket_type)?;
+    }
+    if let Some(issue) = request.issue {
+        Ticket::set_issue(ticket_dir, issue.clone())?;
+    }
 
     info!("Target branch: {}", branch_name);
 
-    // 4. Create worktrees
-    for alias in target_repos {
-        if let Some(repo_def) = config.repositories.get(&alias) {
-            info!("Setting up worktree for '{}'...", alias);
+    // 4. Fetch and create worktrees on a bounded worker pool
+    let work: Vec<(&String, &String, &RepoDefinition)> = repo_branches
+        .iter()
+        .filter_map(|(alias, branch)| {
+            config
+                .repositories
+                .get(alias)
+                .map(|def| (alias, branch, def))
+        })
+        .collect();
+    info!(
+        "Setting up {} worktree(s) with up to {} parallel job(s)",
+        work.len(),
+        jobs
+    );
+    let base_branches = request.template.map(|(_, t)| &t.base_branches);
+    let results = parallel::run_bounded(&work, jobs, |(alias, branch, repo_def)| {
+        let base_branch = base_branches.and_then(|b| b.get(alias.as_str()));
+        setup_worktree(alias, repo_def, ticket_dir, branch, base_branch)
+    });
 
-            let target_worktree_path = ticket_dir.join(&alias);
+    let mut failures = Vec::new();
+    let mut created_aliases = Vec::new();
+    for ((alias, branch, repo_def), result) in work.iter().zip(results) {
+        match result {
+            Ok(created) => {
+                created_aliases.push((*alias, *repo_def));
+                let worktree_path = ticket_dir.join(alias);
+                // Record what git created first so rollback covers it whatever fails next.
+                if created.branch_created {
+                    tx.record(SetupStep::CreatedBranch {
+                        repo_path: repo_def.path.clone(),
+                        branch: branch.to_string(),
+                    });
+                }
+                tx.record(SetupStep::CreatedWorktree {
+                    repo_path: repo_def.path.clone(),
+                    worktree_path: worktree_path.clone(),
+                    worktree_name: created.worktree_name,
+                });
+                let base_branch = base_branches.and_then(|b| b.get(alias.as_str()));
+                if let Err(e) = record_repo(
+                    ticket_dir,
+                    alias,
+                    branch,
+                    base_branch,
+                    repo_def,
+                    &worktree_path,
+                ) {
+                    error!("[{}] {:#}", alias, e);
+                    failures.push(e);
+                }
+            }
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e);
+            }
+        }
+    }
 
-            info!(
-                "Updating repository at {:?} before creating worktree",
-                repo_def.path
-            );
-            git::fetch_and_fast_forward(&repo_def.path, "origin").map_err(|e| {
-                error!(
-                    "Failed to update repository '{}' at {:?}: {}",
-                    alias, repo_def.path, e
-                );
-                e
-            })?;
+    combine_failures(failures, "Failed to set up one or more repositories")?;
 
-            git::create_worktree(&repo_def.path, &target_worktree_path, &branch_name, None)
-                .with_context(|| {
-                    format!(
-                        "Failed to create worktree for '{}' at {:?}",
-                        alias, target_worktree_path
-                    )
-                })?;
-            info!("Created worktree: {:?}", target_worktree_path);
-        }
+    // 5. Run post_setup hooks once every worktree exists
+    for (alias, repo_def) in created_aliases {
+        let worktree = ticket_dir.join(alias);
+        let target = HookTarget {
+            alias,
+            worktree: &worktree,
+            ticket_root: ticket_dir,
+            ticket_id: request.ticket_id,
+        };
+        hooks::run(HookEvent::PostSetup, config, Some(repo_def), &target)?;
     }
 
-    info!("Setup for {} complete!", ticket_id);
+    // 6. Run the template's post_create commands in the ticket directory
+    if let Some((name, template)) = request.template
+        && let Err(e) =
+            hooks::run_post_create(name, &template.post_create, ticket_dir, request.ticket_id)
+    {
+        bail!("Template '{}' failed: {:#}", name, e);
+    }
     Ok(())
 }
 
+/// Record a repo whose worktree was created in the ticket metadata and carry its local files.
+fn record_repo(
+    ticket_dir: &Path,
+    alias: &str,
+    branch: &str,
+    base_branch: Option<&String>,
+    repo_def: &RepoDefinition,
+    worktree_path: &Path,
+) -> Result<()> {
+    Ticket::add_repo_branch(ticket_dir, alias, branch)?;
+    if let Some(base) = base_branch {
+        Ticket::set_repo_base_branch(ticket_dir, alias, base)?;
+    }
+    if let Some(carried) = carry::carry_into_new_worktree(alias, repo_def, worktree_path) {
+        Ticket::set_carried_files(ticket_dir, alias, carried)?;
+    }
+    Ok(())
+}
+
+/// Fetch the source repository and create the ticket worktree for a single alias.
+///
+/// `base_branch` overrides the repo's configured base branch (from a ticket template).
+fn setup_worktree(
+    alias: &str,
+    repo_def: &RepoDefinition,
+    ticket_dir: &Path,
+    branch_name: &str,
+    base_branch: Option<&String>,
+) -> Result<git::CreatedWorktree> {
+    let target_worktree_path = ticket_dir.join(alias);
+
+    info!("[{}] Updating repository at {:?}", alias, repo_def.path);
+    let mut remote = repo_def.remote_options();
+    if let Some(base) = base_branch {
+        remote.base_branch = Some(base);
+    }
+    git::fetch_and_fast_forward(&repo_def.path, &remote).with_context(|| {
+        format!(
+            "Failed to update repository '{}' at {:?}",
+            alias, repo_def.path
+        )
+    })?;
+
+    info!("[{}] Creating worktree on '{}'", alias, branch_name);
+    let created = git::create_worktree(
+        &repo_def.path,
+        &target_worktree_path,
+        branch_name,
+        None,
+        &remote,
+    )
+    .with_context(|| {
+        format!(
+            "Failed to create worktree for '{}' at {:?}",
+            alias, target_worktree_path
+        )
+    })?;
+    info!("[{}] Created worktree: {:?}", alias, target_worktree_path);
+    Ok(created)
+}
+
+/// A completed setup step that can be undone if a later step fails.
+#[derive(Debug)]
+enum SetupStep {
+    /// The ticket directory did not exist and was created.
+    CreatedTicketDir(PathBuf),
+    /// Ticket metadata was written; `previous` holds the prior file contents, if any, and
+    /// `created_stamp_dir` whether the `.tix` directory had to be created for it.
+    WroteMetadata {
+        root: PathBuf,
+        previous: Option<String>,
+        created_stamp_dir: bool,
+    },
+    /// A new local branch was created in the source repository.
+    CreatedBranch { repo_path: PathBuf, branch: String },
+    /// A worktree was added to the source repository.
+    CreatedWorktree {
+        repo_path: PathBuf,
+        worktree_path: PathBuf,
+        worktree_name: String,
+    },
+}
+
+/// Journal of completed setup steps, undone in reverse order on failure.
+#[derive(Debug, Default)]
+struct SetupTransaction {
+    steps: Vec<SetupStep>,
+}
+
+impl SetupTransaction {
+    fn record(&mut self, step: SetupStep) {
+        debug!("Setup step completed: {:?}", step);
+        self.steps.push(step);
+    }
+
+    fn created_ticket_dir(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|s| matches!(s, SetupStep::CreatedTicketDir(_)))
+    }
+
+    /// Undo every recorded step, newest first. Failures are logged and do not stop the rollback.
+    fn rollback(self) {
+        for step in self.steps.into_iter().rev() {
+            if let Err(e) = undo(&step) {
+                warn!("Rollback step {:?} failed: {:#}", step, e);
+            }
+        }
+    }
+}
+
+fn undo(step: &SetupStep) -> Result<()> {
+    match step {
+        SetupStep::CreatedWorktree {
+            repo_path,
+            worktree_path,
+            worktree_name,
+        } => {
+            info!("Rolling back worktree {:?}", worktree_path);
+            if worktree_path.exists() {
+                fs::remove_dir_all(worktree_path)?;
+            }
+            git::remove_worktree(repo_path, worktree_name)
+        }
+        SetupStep::CreatedBranch { repo_path, branch } => {
+            info!("Rolling back branch '{}' in {:?}", branch, repo_path);
+            git::delete_local_branch(repo_path, branch)
+        }
+        SetupStep::WroteMetadata {
+            root,
+            previous,
+            created_stamp_dir,
+        } => {
+            let path = metadata_path(root);
+            match previous {
+                Some(contents) => fs::write(&path, contents)?,
+                None if path.exists() => fs::remove_file(&path)?,
+                None => {}
+            }
+            if let Some(stamp_dir) = path.parent()
+                && *created_stamp_dir
+                && stamp_dir.exists()
+            {
+                fs::remove_dir_all(stamp_dir)?;
+            }
+            Ok(())
+        }
+        SetupStep::CreatedTicketDir(dir) => {
+            info!("Rolling back ticket directory {:?}", dir);
+            if dir.exists() {
+                fs::remove_dir_all(dir)?;
+            }
+            Ok(())
+        }
+    }
+}
+
 /// Sanitize free-form text for inclusion in a git branch name (lowercase, alnum, single hyphens).
 #[allow(dead_code)]
 pub fn sanitize_description(input: &str) -> String {
@@ -1,13 +1,17 @@
 //! Clone all registered repositories into the configured code directory.
 
+use crate::core::commands::common::combine_failures;
 use crate::core::config::{Config, RepoDefinition};
 use crate::core::git;
+use crate::core::parallel;
+use crate::core::plan::{self, Action};
 use anyhow::{Context, Result, bail};
-use log::{debug, info, warn};
+use log::{debug, error, info, warn};
 use std::fs;
 
-/// Run the setup-repos command: clone any missing repositories.
-pub fn run() -> Result<()> {
+/// Run the setup-repos command: clone any missing repositories. With `dry_run`, only print
+/// which ones would be cloned.
+pub fn run(jobs: Option<usize>, dry_run: bool) -> Result<()> {
     let config = Config::load()?;
 
     if config.repositories.is_empty() {
@@ -19,73 +23,60 @@ pub fn run() -> Result<()> {
         bail!("code_directory is not configured; run `tix init` first");
     }
 
-    fs::create_dir_all(&config.code_directory).with_context(|| {
-        format!(
-            "Failed to ensure code directory at {:?}",
-            config.code_directory
-        )
-    })?;
-
     info!(
         "Ensuring repositories are cloned under {:?}",
         config.code_directory
     );
 
     let plan = compute_clone_plan(&config)?;
+    if dry_run {
+        plan::print(&describe_clone_plan(&config, &plan));
+        return Ok(());
+    }
+    fs::create_dir_all(&config.code_directory).with_context(|| {
+        format!(
+            "Failed to ensure code directory at {:?}",
+            config.code_directory
+        )
+    })?;
     if plan.is_empty() {
         info!("All repositories already exist. Nothing to do.");
         return Ok(());
     }
 
-    let mut failures = Vec::new();
-
-    for (alias, repo_def) in plan {
+    let jobs = parallel::resolve_jobs(jobs, config.max_parallel_jobs);
+    let results = parallel::run_bounded(&plan, jobs, |(alias, repo_def)| {
         if let Some(parent) = repo_def.path.parent() {
             fs::create_dir_all(parent).ok();
         }
 
         info!(
-            "Cloning '{}' from {} into {:?}",
+            "[{}] Cloning from {} into {:?}",
             alias, repo_def.url, repo_def.path
         );
 
-        if let Err(e) = git::clone_repo(&repo_def.url, &repo_def.path) {
-            let enriched = e.context(format!(
+        git::clone_repo(&repo_def.url, &repo_def.path, repo_def.remote_name()).with_context(|| {
+            format!(
                 "Failed to clone '{}' from {} into {:?}",
                 alias, repo_def.url, repo_def.path
-            ));
-            failures.push(enriched);
-        } else {
-            info!("Cloned '{}'", alias);
-        }
-    }
+            )
+        })
+    });
 
-    if failures.is_empty() {
-        info!("setup-repos complete.");
-        Ok(())
-    } else {
-        // Bubble the first error with the rest summarized for top-level logging.
-        let mut iter = failures.into_iter();
-        let mut err = iter
-            .next()
-            .unwrap()
-            .context("Failed to clone one or more repositories");
-        let mut summaries = Vec::new();
-        for extra in iter {
-            summaries.push(extra.to_string());
-        }
-        if !summaries.is_empty() {
-            err = err.context(format!(
-                "Additional clone failures:\n{}",
-                summaries
-                    .into_iter()
-                    .map(|s| format!("- {}", s))
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            ));
+    let mut failures = Vec::new();
+    for ((alias, _), result) in plan.iter().zip(results) {
+        match result {
+            Ok(()) => info!("[{}] Cloned", alias),
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                failures.push(e);
+            }
         }
-        Err(err)
     }
+
+    combine_failures(failures, "Failed to clone one or more repositories")?;
+    info!("setup-repos complete.");
+    Ok(())
 }
 
 /// Determine which repositories need cloning (i.e., their target path does not exist).
@@ -109,9 +100,26 @@ pub fn compute_clone_plan(config: &Config) -> Result<Vec<(String, RepoDefinition
         plan.push((alias.clone(), repo_def.clone()));
     }
 
+    plan.sort_by(|a, b| a.0.cmp(&b.0));
     Ok(plan)
 }
 
+/// Actions `run` would take for `plan`.
+fn describe_clone_plan(config: &Config, plan: &[(String, RepoDefinition)]) -> Vec<Action> {
+    let mut actions = Vec::new();
+    if !plan.is_empty() && !config.code_directory.exists() {
+        actions.push(Action::CreateDir(config.code_directory.clone()));
+    }
+    for (alias, repo_def) in plan {
+        actions.push(Action::Clone {
+            alias: alias.clone(),
+            url: repo_def.url.clone(),
+            path: repo_def.path.clone(),
+        });
+    }
+    actions
+}
+
 #[cfg(test)]
 mod tests {
     use super::compute_clone_plan;
@@ -121,7 +129,7 @@ mod tests {
     };
     use std::collections::HashMap;
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn unique_temp_dir() -> PathBuf {
@@ -134,7 +142,7 @@ mod tests {
         path
     }
 
-    fn base_config(root: &PathBuf) -> Config {
+    fn base_config(root: &Path) -> Config {
         Config {
             branch_prefix: defaults::DEFAULT_BRANCH_PREFIX.into(),
             github_base_url: defaults::DEFAULT_GITHUB_BASE_URL.into(),
@@ -144,6 +152,7 @@ mod tests {
             repositories: HashMap::new(),
             plugins: HashMap::new(),
             jira_base_url: None,
+            ..Default::default()
         }
     }
 
@@ -162,6 +171,7 @@ mod tests {
             RepoDefinition {
                 url: "git@github.com:org/existing.git".into(),
                 path: existing_path.clone(),
+                ..Default::default()
             },
         );
         config.repositories.insert(
@@ -169,6 +179,7 @@ mod tests {
             RepoDefinition {
                 url: "git@github.com:org/missing.git".into(),
                 path: missing_path.clone(),
+                ..Default::default()
             },
         );
 
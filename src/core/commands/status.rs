@@ -0,0 +1,314 @@
+//! Show the git state of every worktree in a ticket.
+
+use crate::core::commands::common::locate_ticket_root;
+use crate::core::commands::info::format_status;
+use crate::core::config::Config;
+use crate::core::git::{self, Divergence, WorktreeStatus};
+use crate::core::output::{self, OutputFormat, TicketRecord};
+use crate::core::ticket::{Ticket, TicketStatus};
+use crate::core::tracker;
+use anyhow::{Context, Result};
+use log::info;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// State of a single repo worktree within a ticket.
+#[derive(Debug)]
+pub enum RepoState {
+    /// The worktree directory does not exist.
+    MissingWorktree,
+    /// The directory exists but its git metadata is missing or broken.
+    MissingGitMetadata(String),
+    /// The worktree was inspected successfully.
+    Present(WorktreeStatus),
+}
+
+/// Status report for one alias in the ticket.
+#[derive(Debug)]
+pub struct RepoStatus {
+    pub alias: String,
+    pub path: PathBuf,
+    /// Branch recorded in ticket metadata.
+    pub expected_branch: String,
+    pub state: RepoState,
+}
+
+#[derive(Serialize, Debug, Default)]
+/// Flattened, machine-readable form of a `RepoStatus`.
+pub struct RepoStatusRecord {
+    pub alias: String,
+    pub path: String,
+    pub expected_branch: String,
+    /// One of `ok`, `missing_worktree`, or `missing_git_metadata`.
+    pub state: &'static str,
+    pub error: Option<String>,
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub upstream_ahead: Option<usize>,
+    pub upstream_behind: Option<usize>,
+    pub base: Option<String>,
+    pub base_ahead: Option<usize>,
+    pub base_behind: Option<usize>,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+}
+
+impl From<&RepoStatus> for RepoStatusRecord {
+    fn from(status: &RepoStatus) -> Self {
+        let mut record = RepoStatusRecord {
+            alias: status.alias.clone(),
+            path: status.path.display().to_string(),
+            expected_branch: status.expected_branch.clone(),
+            ..Default::default()
+        };
+        match &status.state {
+            RepoState::MissingWorktree => record.state = "missing_worktree",
+            RepoState::MissingGitMetadata(reason) => {
+                record.state = "missing_git_metadata";
+                record.error = Some(reason.clone());
+            }
+            RepoState::Present(wt) => {
+                record.state = "ok";
+                record.branch = wt.branch.clone();
+                record.upstream = wt.upstream.clone();
+                record.upstream_ahead = wt.upstream_divergence.map(|d| d.ahead);
+                record.upstream_behind = wt.upstream_divergence.map(|d| d.behind);
+                record.base = wt.base.clone();
+                record.base_ahead = wt.base_divergence.map(|d| d.ahead);
+                record.base_behind = wt.base_divergence.map(|d| d.behind);
+                record.staged = wt.staged;
+                record.unstaged = wt.unstaged;
+                record.untracked = wt.untracked;
+            }
+        }
+        record
+    }
+}
+
+#[derive(Serialize)]
+struct StatusDocument {
+    ticket: TicketRecord,
+    repos: Vec<RepoStatusRecord>,
+}
+
+/// Change the lifecycle status of a ticket.
+pub fn set(ticket: Option<&str>, status: TicketStatus) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let previous = Ticket::set_status(&ticket_root, status)?;
+    match previous {
+        Some(prev) if prev == status => info!("Ticket is already {}", status),
+        Some(prev) => info!("Status changed: {} -> {}", prev, status),
+        None => info!("Status set to {}", status),
+    }
+    Ok(())
+}
+
+/// Run the status command.
+pub fn run(ticket: Option<&str>, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+
+    let statuses = collect(&config, &ticket_root, &ticket)?;
+
+    match format {
+        OutputFormat::Json => {
+            let document = StatusDocument {
+                ticket: TicketRecord::new(
+                    &ticket.metadata,
+                    &ticket_root,
+                    tracker::issue_link(&config, &ticket.metadata.id),
+                ),
+                repos: statuses.iter().map(RepoStatusRecord::from).collect(),
+            };
+            return output::print_json(&document);
+        }
+        OutputFormat::Tsv => {
+            let rows: Vec<Vec<String>> = statuses
+                .iter()
+                .map(|s| tsv_row(&RepoStatusRecord::from(s)))
+                .collect();
+            output::print_tsv(
+                &[
+                    "repo",
+                    "state",
+                    "branch",
+                    "upstream",
+                    "upstream_ahead",
+                    "upstream_behind",
+                    "base",
+                    "base_ahead",
+                    "base_behind",
+                    "staged",
+                    "unstaged",
+                    "untracked",
+                ],
+                &rows,
+            );
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    let description = ticket.metadata.description.as_deref().unwrap_or("");
+    println!("[{}] {}", ticket.metadata.id, description);
+    println!("Status: {}", format_status(&ticket.metadata));
+    if statuses.is_empty() {
+        println!("No repositories in this ticket.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<16} {:<36} {:<14} {:<20} {:>6} {:>8} {:>9}",
+        "REPO", "BRANCH", "UPSTREAM", "BASE", "STAGED", "UNSTAGED", "UNTRACKED"
+    );
+    println!("{}", "-".repeat(115));
+    for status in &statuses {
+        println!("{}", format_row(status));
+    }
+    Ok(())
+}
+
+/// Gather the status of every repo recorded in the ticket metadata, sorted by alias.
+pub fn collect(config: &Config, ticket_root: &Path, ticket: &Ticket) -> Result<Vec<RepoStatus>> {
+    let mut aliases: Vec<&String> = ticket.metadata.repo_branches.keys().collect();
+    aliases.sort();
+
+    let mut statuses = Vec::new();
+    for alias in aliases {
+        let path = ticket_root.join(alias);
+        let base = config.repositories.get(alias).and_then(|def| {
+            git::default_branch_for(&def.path, &ticket.metadata.remote_options(alias, def))
+        });
+        let state = inspect(&path, base.as_deref());
+        statuses.push(RepoStatus {
+            alias: alias.clone(),
+            path,
+            expected_branch: ticket.metadata.repo_branches[alias].clone(),
+            state,
+        });
+    }
+    Ok(statuses)
+}
+
+fn inspect(path: &Path, base: Option<&str>) -> RepoState {
+    if !path.exists() {
+        return RepoState::MissingWorktree;
+    }
+    if !path.join(".git").exists() {
+        return RepoState::MissingGitMetadata("no .git entry".to_string());
+    }
+    match git::worktree_status(path, base) {
+        Ok(status) => RepoState::Present(status),
+        Err(e) => RepoState::MissingGitMetadata(format!("{:#}", e)),
+    }
+}
+
+fn format_row(status: &RepoStatus) -> String {
+    match &status.state {
+        RepoState::MissingWorktree => format!(
+            "{:<16} {:<36} (worktree directory missing: {})",
+            status.alias,
+            status.expected_branch,
+            status.path.display()
+        ),
+        RepoState::MissingGitMetadata(reason) => format!(
+            "{:<16} {:<36} (git metadata missing: {})",
+            status.alias, status.expected_branch, reason
+        ),
+        RepoState::Present(wt) => {
+            let branch = match &wt.branch {
+                Some(b) if b != &status.expected_branch => {
+                    format!("{} (expected {})", b, status.expected_branch)
+                }
+                Some(b) => b.clone(),
+                None => "(detached)".to_string(),
+            };
+            let upstream = if wt.upstream.is_some() {
+                format_divergence(wt.upstream_divergence)
+            } else {
+                "(none)".to_string()
+            };
+            let base = match &wt.base {
+                Some(name) => format!("{} {}", name, format_divergence(wt.base_divergence)),
+                None => "(unknown)".to_string(),
+            };
+            format!(
+                "{:<16} {:<36} {:<14} {:<20} {:>6} {:>8} {:>9}",
+                status.alias, branch, upstream, base, wt.staged, wt.unstaged, wt.untracked
+            )
+        }
+    }
+}
+
+fn tsv_row(record: &RepoStatusRecord) -> Vec<String> {
+    fn opt<T: ToString>(value: &Option<T>) -> String {
+        value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+    }
+    vec![
+        record.alias.clone(),
+        record.state.to_string(),
+        opt(&record.branch),
+        opt(&record.upstream),
+        opt(&record.upstream_ahead),
+        opt(&record.upstream_behind),
+        opt(&record.base),
+        opt(&record.base_ahead),
+        opt(&record.base_behind),
+        record.staged.to_string(),
+        record.unstaged.to_string(),
+        record.untracked.to_string(),
+    ]
+}
+
+/// Format ahead/behind counts as `+ahead/-behind`.
+fn format_divergence(divergence: Option<Divergence>) -> String {
+    match divergence {
+        Some(d) => format!("+{}/-{}", d.ahead, d.behind),
+        None => "?".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_divergence_shows_counts() {
+        assert_eq!(
+            format_divergence(Some(Divergence {
+                ahead: 2,
+                behind: 1
+            })),
+            "+2/-1"
+        );
+        assert_eq!(format_divergence(None), "?");
+    }
+
+    #[test]
+    fn format_row_reports_missing_worktree() {
+        let status = RepoStatus {
+            alias: "api".into(),
+            path: PathBuf::from("/tickets/JIRA-1/api"),
+            expected_branch: "feature/JIRA-1".into(),
+            state: RepoState::MissingWorktree,
+        };
+        assert!(format_row(&status).contains("worktree directory missing"));
+    }
+
+    #[test]
+    fn format_row_flags_unexpected_branch() {
+        let status = RepoStatus {
+            alias: "api".into(),
+            path: PathBuf::from("/tickets/JIRA-1/api"),
+            expected_branch: "feature/JIRA-1".into(),
+            state: RepoState::Present(WorktreeStatus {
+                branch: Some("main".into()),
+                ..Default::default()
+            }),
+        };
+        assert!(format_row(&status).contains("main (expected feature/JIRA-1)"));
+    }
+}
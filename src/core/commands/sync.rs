@@ -0,0 +1,398 @@
+//! Sync command: fetch and update every ticket branch onto its base branch.
+//!
+//! Progress is recorded in `.tix/sync.toml` so a run that stops on conflicts can be resumed
+//! with `tix sync` once the conflicts are resolved, or abandoned with `tix sync --abort`.
+
+use crate::core::commands::common::locate_ticket_root;
+use crate::core::config::{Config, RepoDefinition};
+use crate::core::git::{self, RemoteOptions, SyncOutcome};
+use crate::core::ticket::{Ticket, stamp_dir};
+use anyhow::{Context, Result, bail};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SYNC_STATE_FILE: &str = "sync.toml";
+
+/// How a ticket branch is updated onto its base.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStrategy {
+    Merge,
+    Rebase,
+}
+
+impl SyncStrategy {
+    fn as_str(self) -> &'static str {
+        match self {
+            SyncStrategy::Merge => "merge",
+            SyncStrategy::Rebase => "rebase",
+        }
+    }
+}
+
+/// Per-repo progress of a sync run.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepoSyncState {
+    /// Not yet synced.
+    Pending,
+    /// Synced successfully.
+    Done,
+    /// Could not be synced this run (unregistered, missing worktree or uncommitted changes).
+    /// Skipped repos are retried on resume but do not keep a sync in progress.
+    Skipped,
+    /// Stopped on conflicts that need manual resolution.
+    Conflict,
+}
+
+/// Resumable state of an interrupted sync, stored in `.tix/sync.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SyncState {
+    pub strategy: SyncStrategy,
+    #[serde(default)]
+    pub repos: BTreeMap<String, RepoSyncState>,
+}
+
+impl SyncState {
+    fn path(ticket_root: &Path) -> PathBuf {
+        stamp_dir(ticket_root).join(SYNC_STATE_FILE)
+    }
+
+    fn load(ticket_root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(ticket_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read sync state {:?}", path))?;
+        let state = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse sync state {:?}", path))?;
+        Ok(Some(state))
+    }
+
+    fn save(&self, ticket_root: &Path) -> Result<()> {
+        let path = Self::path(ticket_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write sync state {:?}", path))
+    }
+
+    fn clear(ticket_root: &Path) -> Result<()> {
+        let path = Self::path(ticket_root);
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove sync state {:?}", path))?;
+        }
+        Ok(())
+    }
+
+    /// Repos that still need this sync to be resumed.
+    fn unfinished(&self) -> Vec<&str> {
+        self.aliases_in(|s| matches!(s, RepoSyncState::Pending | RepoSyncState::Conflict))
+    }
+
+    fn skipped(&self) -> Vec<&str> {
+        self.aliases_in(|s| s == RepoSyncState::Skipped)
+    }
+
+    fn aliases_in(&self, keep: impl Fn(RepoSyncState) -> bool) -> Vec<&str> {
+        self.repos
+            .iter()
+            .filter(|(_, s)| keep(**s))
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+}
+
+/// Pick the strategy for this run, honouring a saved in-progress sync.
+fn resolve_strategy(
+    requested: Option<SyncStrategy>,
+    saved: Option<&SyncState>,
+) -> Result<SyncStrategy> {
+    match (requested, saved) {
+        (Some(req), Some(state)) if req != state.strategy => bail!(
+            "A sync using --{} is already in progress. Finish it with 'tix sync' or run 'tix sync --abort' first.",
+            state.strategy.as_str()
+        ),
+        (_, Some(state)) => Ok(state.strategy),
+        (Some(req), None) => Ok(req),
+        (None, None) => Ok(SyncStrategy::Merge),
+    }
+}
+
+/// Run the sync command.
+pub fn run(ticket: Option<&str>, rebase: bool, merge: bool, abort: bool) -> Result<()> {
+    let config = Config::load()?;
+    let ticket_root = locate_ticket_root(ticket, &config)?;
+    let ticket = Ticket::load(&ticket_root).context("Failed to load ticket metadata")?;
+    let saved = SyncState::load(&ticket_root)?;
+
+    if abort {
+        return abort_sync(&ticket_root, saved);
+    }
+
+    let requested = if rebase {
+        Some(SyncStrategy::Rebase)
+    } else if merge {
+        Some(SyncStrategy::Merge)
+    } else {
+        None
+    };
+    let strategy = resolve_strategy(requested, saved.as_ref())?;
+    let resuming = saved.is_some();
+
+    let mut aliases: Vec<String> = ticket.metadata.repo_branches.keys().cloned().collect();
+    aliases.sort();
+    let mut state = saved.unwrap_or(SyncState {
+        strategy,
+        repos: aliases
+            .iter()
+            .map(|a| (a.clone(), RepoSyncState::Pending))
+            .collect(),
+    });
+    // Repos removed from the ticket since the sync started no longer need syncing.
+    state.repos.retain(|alias, _| aliases.contains(alias));
+    if resuming {
+        info!(
+            "Resuming {} sync for {}",
+            strategy.as_str(),
+            ticket.metadata.id
+        );
+    } else {
+        info!(
+            "Syncing {} ({}) onto base branches",
+            ticket.metadata.id,
+            strategy.as_str()
+        );
+    }
+    state.save(&ticket_root)?;
+
+    let mut failed = Vec::new();
+    for alias in &aliases {
+        let previous = state
+            .repos
+            .get(alias)
+            .copied()
+            .unwrap_or(RepoSyncState::Pending);
+        if previous == RepoSyncState::Done {
+            debug!("[{}] Already synced; skipping", alias);
+            continue;
+        }
+
+        let Some(repo_def) = config.repositories.get(alias) else {
+            warn!("[{}] Not registered in config; skipping", alias);
+            state.repos.insert(alias.clone(), RepoSyncState::Skipped);
+            continue;
+        };
+        let worktree_path = ticket_root.join(alias);
+        if !worktree_path.exists() {
+            warn!(
+                "[{}] Worktree missing at {:?}; skipping",
+                alias, worktree_path
+            );
+            state.repos.insert(alias.clone(), RepoSyncState::Skipped);
+            continue;
+        }
+
+        let carried = ticket.metadata.carried_files(alias);
+        let remote = ticket.metadata.remote_options(alias, repo_def);
+        let result = sync_repo(alias, repo_def, &remote, &worktree_path, carried, strategy);
+        let next = match result {
+            Ok(next) => next,
+            Err(e) => {
+                error!("[{}] {:#}", alias, e);
+                failed.push(alias.clone());
+                previous
+            }
+        };
+        state.repos.insert(alias.clone(), next);
+        state.save(&ticket_root)?;
+    }
+
+    let unresolved: Vec<&str> = state
+        .repos
+        .iter()
+        .filter(|(_, s)| **s == RepoSyncState::Conflict)
+        .map(|(alias, _)| alias.as_str())
+        .collect();
+    if !unresolved.is_empty() {
+        let hint = match strategy {
+            SyncStrategy::Merge => "resolve the conflicts and commit",
+            SyncStrategy::Rebase => "resolve the conflicts and run 'git rebase --continue'",
+        };
+        bail!(
+            "Sync stopped on conflicts in: {}\nIn each repo, {}, then re-run 'tix sync' to resume (or 'tix sync --abort').",
+            unresolved.join(", "),
+            hint
+        );
+    }
+    if !failed.is_empty() {
+        bail!(
+            "Failed to sync: {}\nFix the problem and re-run 'tix sync' to resume.",
+            failed.join(", ")
+        );
+    }
+
+    // Skipped repos do not keep the sync in progress.
+    if state.unfinished().is_empty() {
+        SyncState::clear(&ticket_root)?;
+    }
+    let skipped = state.skipped();
+    if skipped.is_empty() {
+        info!("Sync for {} complete!", ticket.metadata.id);
+    } else {
+        warn!(
+            "Skipped: {}. Fix them and re-run 'tix sync' to sync them.",
+            skipped.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Fetch, resolve the base, and update one worktree. Returns the new state for the repo.
+fn sync_repo(
+    alias: &str,
+    repo_def: &RepoDefinition,
+    remote: &RemoteOptions,
+    worktree_path: &Path,
+    carried: &[PathBuf],
+    strategy: SyncStrategy,
+) -> Result<RepoSyncState> {
+    if git::operation_in_progress(worktree_path)? {
+        warn!(
+            "[{}] A merge or rebase is still in progress in {:?}",
+            alias, worktree_path
+        );
+        return Ok(RepoSyncState::Conflict);
+    }
+    if !git::is_clean_ignoring(worktree_path, carried)? {
+        warn!(
+            "[{}] Worktree has uncommitted changes; skipping (commit or stash them first)",
+            alias
+        );
+        return Ok(RepoSyncState::Skipped);
+    }
+
+    let repo_path = repo_def.path.as_path();
+    info!("[{}] Fetching {:?}", alias, repo_path);
+    git::fetch_and_fast_forward(repo_path, remote)
+        .with_context(|| format!("Failed to fetch repository at {:?}", repo_path))?;
+
+    let base =
+        git::sync_base_ref(repo_path, remote).context("Could not determine the base branch")?;
+    let base_name = git::short_ref_name(&base);
+
+    let outcome = match strategy {
+        SyncStrategy::Merge => git::merge_base_into(worktree_path, &base),
+        SyncStrategy::Rebase => git::rebase_onto_base(worktree_path, &base),
+    }
+    .with_context(|| format!("Failed to {} onto {}", strategy.as_str(), base_name))?;
+
+    match outcome {
+        SyncOutcome::UpToDate => info!("[{}] Already up to date with {}", alias, base_name),
+        SyncOutcome::FastForwarded => info!("[{}] Fast-forwarded to {}", alias, base_name),
+        SyncOutcome::Merged => info!("[{}] Merged {}", alias, base_name),
+        SyncOutcome::Rebased(n) => {
+            info!("[{}] Rebased {} commit(s) onto {}", alias, n, base_name)
+        }
+        SyncOutcome::Conflicts(paths) => {
+            error!(
+                "[{}] Conflicts while syncing onto {}: {}",
+                alias,
+                base_name,
+                paths.join(", ")
+            );
+            return Ok(RepoSyncState::Conflict);
+        }
+    }
+    Ok(RepoSyncState::Done)
+}
+
+fn abort_sync(ticket_root: &Path, saved: Option<SyncState>) -> Result<()> {
+    let Some(state) = saved else {
+        info!("No sync in progress.");
+        return Ok(());
+    };
+    for (alias, repo_state) in &state.repos {
+        if *repo_state != RepoSyncState::Conflict {
+            continue;
+        }
+        let worktree_path = ticket_root.join(alias);
+        match git::abort_in_progress(&worktree_path) {
+            Ok(true) => info!(
+                "[{}] Aborted in-progress {}",
+                alias,
+                state.strategy.as_str()
+            ),
+            Ok(false) => debug!("[{}] Nothing to abort", alias),
+            Err(e) => warn!("[{}] Failed to abort: {:#}", alias, e),
+        }
+    }
+    SyncState::clear(ticket_root)?;
+    info!("Sync aborted.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolve_strategy_defaults_to_merge_and_respects_saved_state() {
+        assert_eq!(resolve_strategy(None, None).unwrap(), SyncStrategy::Merge);
+        assert_eq!(
+            resolve_strategy(Some(SyncStrategy::Rebase), None).unwrap(),
+            SyncStrategy::Rebase
+        );
+
+        let saved = SyncState {
+            strategy: SyncStrategy::Rebase,
+            repos: BTreeMap::new(),
+        };
+        assert_eq!(
+            resolve_strategy(None, Some(&saved)).unwrap(),
+            SyncStrategy::Rebase
+        );
+        assert!(resolve_strategy(Some(SyncStrategy::Merge), Some(&saved)).is_err());
+    }
+
+    #[test]
+    fn sync_state_round_trips_and_clears() {
+        let temp = TempDir::new().unwrap();
+        let mut repos = BTreeMap::new();
+        repos.insert("api".to_string(), RepoSyncState::Done);
+        repos.insert("web".to_string(), RepoSyncState::Conflict);
+        let state = SyncState {
+            strategy: SyncStrategy::Merge,
+            repos,
+        };
+
+        state.save(temp.path()).unwrap();
+        let loaded = SyncState::load(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.unfinished(), vec!["web"]);
+        assert!(loaded.skipped().is_empty());
+
+        SyncState::clear(temp.path()).unwrap();
+        assert!(SyncState::load(temp.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn skipped_repos_do_not_keep_a_sync_unfinished() {
+        let mut repos = BTreeMap::new();
+        repos.insert("api".to_string(), RepoSyncState::Done);
+        repos.insert("web".to_string(), RepoSyncState::Skipped);
+        let state = SyncState {
+            strategy: SyncStrategy::Rebase,
+            repos,
+        };
+        assert!(state.unfinished().is_empty());
+        assert_eq!(state.skipped(), vec!["web"]);
+    }
+}
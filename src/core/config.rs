@@ -1,19 +1,331 @@
 //! Configuration model and persistence for tix.
 
-use anyhow::{Context, Result};
+use crate::core::git::RemoteOptions;
+use crate::core::hooks::HookEvent;
+use crate::core::{branch, defaults, ticket_id};
+use anyhow::{Context, Result, bail};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{env, path::Path};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 /// Definition of a registered repository (remote URL and local path).
 pub struct RepoDefinition {
     /// Remote URL (e.g., `git@github.com:owner/repo.git`).
     pub url: String,
     /// Local code path (e.g., `~/code/repo`).
     pub path: PathBuf,
+    /// Remote to fetch from and push to (defaults to `origin`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    /// Branch new ticket branches start from (defaults to the remote's default branch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Overrides the global `branch_prefix` for this repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_prefix: Option<String>,
+    /// Refspecs to fetch instead of every branch (e.g., `+refs/heads/main:refs/remotes/origin/main`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fetch_refspecs: Vec<String>,
+    /// Glob patterns of untracked/ignored files (e.g., `.env`) carried from `path` into new worktrees.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carry_files: Vec<String>,
+    /// Whether `carry_files` are copied (default) or symlinked.
+    #[serde(default, skip_serializing_if = "CarryMode::is_copy")]
+    pub carry_mode: CarryMode,
+    /// Hooks for this repository, run after the global hooks for the same event.
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+}
+
+impl RepoDefinition {
+    /// Remote name for this repository.
+    pub fn remote_name(&self) -> &str {
+        self.remote.as_deref().unwrap_or(defaults::DEFAULT_REMOTE)
+    }
+
+    /// Remote settings passed to git operations.
+    pub fn remote_options(&self) -> RemoteOptions<'_> {
+        RemoteOptions {
+            remote: self.remote_name(),
+            base_branch: self.base_branch.as_deref(),
+            fetch_refspecs: &self.fetch_refspecs,
+        }
+    }
+
+    fn validate(&self, alias: &str) -> Result<()> {
+        if let Some(remote) = &self.remote
+            && !git2::Remote::is_valid_name(remote)
+        {
+            bail!(
+                "repositories.{}.remote '{}' is not a valid remote name",
+                alias,
+                remote
+            );
+        }
+        for (field, value) in [
+            ("base_branch", &self.base_branch),
+            ("branch_prefix", &self.branch_prefix),
+        ] {
+            if let Some(value) = value
+                && !git2::Reference::is_valid_name(&format!("refs/heads/{}", value))
+            {
+                bail!(
+                    "repositories.{}.{} '{}' is not a valid branch name",
+                    alias,
+                    field,
+                    value
+                );
+            }
+        }
+        if self.fetch_refspecs.iter().any(|r| r.trim().is_empty()) {
+            bail!(
+                "repositories.{}.fetch_refspecs contains an empty refspec",
+                alias
+            );
+        }
+        for pattern in &self.carry_files {
+            let path = Path::new(pattern);
+            if pattern.trim().is_empty()
+                || path.is_absolute()
+                || path
+                    .components()
+                    .any(|c| c == std::path::Component::ParentDir)
+            {
+                bail!(
+                    "repositories.{}.carry_files entry '{}' must be a non-empty path relative to the repository",
+                    alias,
+                    pattern
+                );
+            }
+        }
+        self.hooks
+            .validate()
+            .with_context(|| format!("Invalid repositories.{}.hooks", alias))?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// How `carry_files` are placed into a worktree.
+pub enum CarryMode {
+    /// Copy the file; later edits in the main checkout need `tix refresh-files`.
+    #[default]
+    Copy,
+    /// Symlink to the file in the main checkout.
+    Symlink,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Case normalization applied to ticket ids before they name a directory.
+pub enum TicketIdCase {
+    /// Keep the id as typed.
+    #[default]
+    Preserve,
+    /// Upper-case the id (`jira-123` becomes `JIRA-123`).
+    Upper,
+    /// Lower-case the id.
+    Lower,
+}
+
+impl TicketIdCase {
+    /// True for the default policy (used to keep it out of the saved config).
+    pub fn is_preserve(&self) -> bool {
+        *self == TicketIdCase::Preserve
+    }
+}
+
+impl CarryMode {
+    /// True for the default mode (used to keep it out of the saved config).
+    pub fn is_copy(&self) -> bool {
+        *self == CarryMode::Copy
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// What to do when a hook fails or times out.
+pub enum HookFailurePolicy {
+    /// Log the failure and continue.
+    #[default]
+    Warn,
+    /// Stop the command with an error.
+    Abort,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// A shell command run at a lifecycle event.
+pub struct HookDefinition {
+    /// Command passed to the shell (e.g., `npm ci`).
+    pub command: String,
+    /// Seconds before the hook is killed (defaults to 300).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Failure policy (`warn` or `abort`, defaults to `warn`).
+    #[serde(default)]
+    pub on_failure: HookFailurePolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+/// Hooks keyed by lifecycle event (`[hooks.post_setup]`, `[repositories.<alias>.hooks.post_add]`, ...).
+pub struct HooksConfig {
+    /// Run in each new worktree after `tix setup` creates it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_setup: Option<HookDefinition>,
+    /// Run in the new worktree after `tix add` creates it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_add: Option<HookDefinition>,
+    /// Run in the worktree before `tix remove` deletes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_remove: Option<HookDefinition>,
+    /// Run in each worktree before `tix destroy` deletes the ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_destroy: Option<HookDefinition>,
+}
+
+impl HooksConfig {
+    /// True when no hook is configured.
+    pub fn is_empty(&self) -> bool {
+        HookEvent::ALL
+            .iter()
+            .all(|event| self.get(*event).is_none())
+    }
+
+    /// Hook configured for `event`, if any.
+    pub fn get(&self, event: HookEvent) -> Option<&HookDefinition> {
+        match event {
+            HookEvent::PostSetup => self.post_setup.as_ref(),
+            HookEvent::PostAdd => self.post_add.as_ref(),
+            HookEvent::PreRemove => self.pre_remove.as_ref(),
+            HookEvent::PreDestroy => self.pre_destroy.as_ref(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        for event in HookEvent::ALL {
+            let Some(hook) = self.get(event) else {
+                continue;
+            };
+            if hook.command.trim().is_empty() {
+                bail!("{} has an empty command", event);
+            }
+            if hook.timeout_secs == Some(0) {
+                bail!("{}.timeout_secs must be at least 1", event);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+/// A reusable ticket layout (`[templates.<name>]`), applied with `tix setup --template <name>`.
+pub struct TicketTemplate {
+    /// Repo aliases or `@group` names included in tickets created from the template.
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Overrides the global `branch_prefix` (a repo's own `branch_prefix` still wins).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_prefix: Option<String>,
+    /// Base branch per repo alias, overriding the repo's `base_branch`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub base_branches: HashMap<String, String>,
+    /// Shell commands run in the ticket directory once every worktree exists.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+    /// Description used when `setup` is run without `--description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl TicketTemplate {
+    fn validate(&self) -> Result<()> {
+        if let Some(prefix) = &self.branch_prefix {
+            if prefix.trim().is_empty() {
+                bail!("branch_prefix must not be empty");
+            }
+            if !git2::Reference::is_valid_name(&format!("refs/heads/{}", prefix)) {
+                bail!("branch_prefix '{}' is not a valid branch name", prefix);
+            }
+        }
+        let mut base_branches: Vec<_> = self.base_branches.iter().collect();
+        base_branches.sort();
+        for (alias, branch) in base_branches {
+            if branch.trim().is_empty() {
+                bail!("base_branches.{} must not be empty", alias);
+            }
+            if !git2::Reference::is_valid_name(&format!("refs/heads/{}", branch)) {
+                bail!(
+                    "base_branches.{} '{}' is not a valid branch name",
+                    alias,
+                    branch
+                );
+            }
+        }
+        if self.post_create.iter().any(|c| c.trim().is_empty()) {
+            bail!("post_create contains an empty command");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Issue tracker backend.
+pub enum TrackerKind {
+    /// Jira (issue keys like `PROJ-123`).
+    #[default]
+    Jira,
+    /// GitHub Issues (ticket ids like `123` or `GH-123`).
+    GitHub,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+/// Issue tracker settings (`[tracker]`). Without this section, `jira_base_url` selects Jira.
+pub struct TrackerConfig {
+    /// Backend (`jira` or `github`, defaults to `jira`).
+    #[serde(default)]
+    pub kind: TrackerKind,
+    /// Base URL for issue links (Jira: defaults to `jira_base_url`; GitHub: defaults to
+    /// `<github_base_url>/<repository>/issues`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// REST API base URL (Jira: defaults to `jira_base_url` without `/browse`; GitHub: derived
+    /// from `github_base_url`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// GitHub repository holding the issues (`owner/name`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// Branch prefix per issue type (Jira issue type or GitHub label), matched case-insensitively.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub issue_type_prefixes: HashMap<String, String>,
+}
+
+impl TrackerConfig {
+    fn validate(&self, config: &Config) -> Result<()> {
+        match self.kind {
+            TrackerKind::Jira => {
+                if self.url.is_none() && self.api_url.is_none() && config.jira_base_url.is_none() {
+                    bail!("kind = \"jira\" needs url, api_url or jira_base_url");
+                }
+            }
+            TrackerKind::GitHub => match self.repository.as_deref() {
+                Some(repository)
+                    if repository.split_once('/').is_some_and(|(owner, name)| {
+                        !owner.is_empty() && !name.is_empty() && !name.contains('/')
+                    }) => {}
+                Some(repository) => {
+                    bail!("repository must be 'owner/name', got '{}'", repository)
+                }
+                None => bail!("kind = \"github\" needs repository = \"owner/name\""),
+            },
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -46,6 +358,14 @@ pub struct Config {
     /// Map of repository aliases to their definitions.
     pub repositories: HashMap<String, RepoDefinition>,
 
+    /// Regex every ticket id must fully match (e.g., `[A-Z]+-[0-9]+`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket_id_pattern: Option<String>,
+
+    /// Case normalization for ticket ids (`preserve`, `upper` or `lower`).
+    #[serde(default, skip_serializing_if = "TicketIdCase::is_preserve")]
+    pub ticket_id_case: TicketIdCase,
+
     /// Map of plugin names to their definitions.
     #[serde(default)]
     pub plugins: HashMap<String, PluginDefinition>,
@@ -53,12 +373,77 @@ pub struct Config {
     /// Optional base URL for Jira (e.g., `https://company.atlassian.net/browse`).
     #[serde(default)]
     pub jira_base_url: Option<String>,
+
+    /// Issue tracker used for links and `setup --from-tracker`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracker: Option<TrackerConfig>,
+
+    /// Maximum number of repositories processed concurrently (fetch, worktree creation, clone).
+    #[serde(default)]
+    pub max_parallel_jobs: Option<usize>,
+
+    /// Branch name template (default `{prefix}/{ticket}-{slug}`).
+    #[serde(default)]
+    pub branch_template: Option<String>,
+
+    /// Branch templates keyed by ticket type (`tix setup --type <type>`), overriding `branch_template`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub branch_templates: HashMap<String, String>,
+
+    /// Maximum length of the `{slug}` placeholder.
+    #[serde(default)]
+    pub branch_slug_max_length: Option<usize>,
+
+    /// Named lists of repository aliases, referenced as `@name` by `setup`, `add` and `remove`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub groups: HashMap<String, Vec<String>>,
+
+    /// Hooks run for every repository (before any per-repository hook for the same event).
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+    /// Ticket templates selectable with `tix setup --template <name>`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub templates: HashMap<String, TicketTemplate>,
+}
+
+/// Prefix marking a group reference in repo arguments (e.g., `@backend`).
+pub const GROUP_PREFIX: char = '@';
+
+/// Check that `name` can be used as a group name (and referenced as `@name`).
+pub fn validate_group_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("Group name cannot be empty");
+    }
+    if name
+        .chars()
+        .any(|c| c.is_whitespace() || c == ',' || c == GROUP_PREFIX)
+    {
+        bail!(
+            "Group name '{}' must not contain whitespace, ',' or '{}'",
+            name,
+            GROUP_PREFIX
+        );
+    }
+    Ok(())
 }
 
 impl Config {
     /// Load configuration from the OS config directory (e.g., `~/.config/tix/config.toml`).
-    /// Returns `Config::default()` if the file does not exist.
+    /// Returns `Config::default()` if the file does not exist. Errors if validation fails.
     pub fn load() -> Result<Self> {
+        let config = Self::load_unvalidated()?;
+        if let Err(e) = config.validate() {
+            bail!(
+                "Invalid configuration in {:?}: {:#} (fix it with 'tix config --edit')",
+                Self::config_path().unwrap_or_default(),
+                e
+            );
+        }
+        Ok(config)
+    }
+
+    /// Load configuration without validating it, so `tix config` can repair a bad file.
+    pub fn load_unvalidated() -> Result<Self> {
         let config_path = Self::config_path()?;
 
         if !config_path.exists() {
@@ -71,6 +456,101 @@ impl Config {
         Ok(config)
     }
 
+    /// Check values that cannot be expressed by the TOML schema alone.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(template) = &self.branch_template {
+            branch::validate(template).context("Invalid branch_template")?;
+        }
+        for (ticket_type, template) in &self.branch_templates {
+            branch::validate(template)
+                .with_context(|| format!("Invalid branch_templates.{}", ticket_type))?;
+        }
+        if self.branch_slug_max_length == Some(0) {
+            bail!("branch_slug_max_length must be at least 1");
+        }
+        if let Some(pattern) = &self.ticket_id_pattern {
+            ticket_id::compile_pattern(pattern)?;
+        }
+        let mut aliases: Vec<&String> = self.repositories.keys().collect();
+        aliases.sort();
+        for alias in aliases {
+            self.repositories[alias].validate(alias)?;
+        }
+        self.hooks.validate().context("Invalid hooks")?;
+        let mut groups: Vec<&String> = self.groups.keys().collect();
+        groups.sort();
+        for group in groups {
+            validate_group_name(group).context("Invalid [groups] entry")?;
+        }
+        if let Some(tracker) = &self.tracker {
+            tracker.validate(self).context("Invalid [tracker]")?;
+        }
+        let mut templates: Vec<&String> = self.templates.keys().collect();
+        templates.sort();
+        for name in templates {
+            self.templates[name]
+                .validate()
+                .with_context(|| format!("Invalid templates.{}", name))?;
+        }
+        Ok(())
+    }
+
+    /// Look up a ticket template by name.
+    pub fn template(&self, name: &str) -> Result<&TicketTemplate> {
+        match self.templates.get(name) {
+            Some(template) => Ok(template),
+            None => {
+                let mut known: Vec<&String> = self.templates.keys().collect();
+                known.sort();
+                if known.is_empty() {
+                    bail!(
+                        "Unknown template '{}' (no [templates] are configured)",
+                        name
+                    );
+                }
+                let known: Vec<&str> = known.iter().map(|s| s.as_str()).collect();
+                bail!(
+                    "Unknown template '{}' (available: {})",
+                    name,
+                    known.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Branch template for `ticket_type`, falling back to `branch_template` and the default.
+    pub fn branch_template_for(&self, ticket_type: Option<&str>) -> &str {
+        ticket_type
+            .and_then(|t| self.branch_templates.get(t))
+            .or(self.branch_template.as_ref())
+            .map(|s| s.as_str())
+            .unwrap_or(defaults::DEFAULT_BRANCH_TEMPLATE)
+    }
+
+    /// Expand `@group` references in `inputs` to their member aliases, keeping order and
+    /// dropping duplicates. Plain aliases are passed through unchanged.
+    pub fn expand_aliases(&self, inputs: &[String]) -> Result<Vec<String>> {
+        let mut expanded: Vec<String> = Vec::new();
+        for input in inputs {
+            let members = match input.strip_prefix(GROUP_PREFIX) {
+                Some(group) => match self.groups.get(group) {
+                    Some(members) => members.as_slice(),
+                    None => bail!(
+                        "Unknown repository group '{}' (see 'tix groups list')",
+                        group
+                    ),
+                },
+                None => std::slice::from_ref(input),
+            };
+            for alias in members {
+                if !expanded.contains(alias) {
+                    expanded.push(alias.clone());
+                }
+            }
+        }
+        Ok(expanded)
+    }
+
     /// Persist the configuration to the OS config directory, creating it if needed.
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
@@ -113,3 +593,52 @@ fn xdg_config_home_path() -> Option<PathBuf> {
     }
     Some(dir.join("tix"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_aliases_resolves_groups() {
+        let mut config = Config::default();
+        config
+            .groups
+            .insert("backend".into(), vec!["api".into(), "worker".into()]);
+
+        let expanded = config
+            .expand_aliases(&["web".into(), "@backend".into(), "api".into()])
+            .unwrap();
+        assert_eq!(expanded, vec!["web", "api", "worker"]);
+        assert!(config.expand_aliases(&["@frontend".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_group_name_rejects_separators() {
+        assert!(validate_group_name("backend").is_ok());
+        assert!(validate_group_name("").is_err());
+        assert!(validate_group_name("@backend").is_err());
+        assert!(validate_group_name("back end").is_err());
+    }
+
+    #[test]
+    fn template_validate_rejects_invalid_branch_names() {
+        let mut template = TicketTemplate {
+            branch_prefix: Some("feature".into()),
+            ..Default::default()
+        };
+        template
+            .base_branches
+            .insert("api".into(), "develop".into());
+        assert!(template.validate().is_ok());
+
+        template.branch_prefix = Some("bad..prefix".into());
+        assert!(template.validate().is_err());
+
+        template.branch_prefix = None;
+        template
+            .base_branches
+            .insert("api".into(), "dev elop".into());
+        let err = template.validate().unwrap_err().to_string();
+        assert!(err.contains("base_branches.api 'dev elop'"), "{}", err);
+    }
+}
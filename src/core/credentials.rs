@@ -0,0 +1,88 @@
+//! Credential lookup for HTTP APIs (environment variables, then `git credential fill`).
+
+use anyhow::{Context, Result, bail};
+use log::debug;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// A username/password (or token) pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credential {
+    pub username: String,
+    pub password: String,
+}
+
+/// Ask git's configured credential helpers for `url` (`git credential fill`).
+///
+/// Terminal prompts are disabled, so this returns `None` instead of blocking when no helper
+/// has a credential stored.
+pub fn git_credential_fill(url: &str) -> Result<Option<Credential>> {
+    let (protocol, rest) = url
+        .split_once("://")
+        .with_context(|| format!("Invalid URL '{}'", url))?;
+    let host = rest.split('/').next().unwrap_or_default();
+    if host.is_empty() {
+        bail!("Invalid URL '{}'", url);
+    }
+
+    let mut child = Command::new("git")
+        .args(["credential", "fill"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run 'git credential fill'")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        write!(stdin, "protocol={}\nhost={}\n\n", protocol, host)?;
+    }
+    let output = child
+        .wait_with_output()
+        .context("Failed to run 'git credential fill'")?;
+    if !output.status.success() {
+        debug!(
+            "git credential fill found nothing for {}: {}",
+            host,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Ok(None);
+    }
+    Ok(parse_credential(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the `key=value` lines printed by `git credential fill`.
+fn parse_credential(output: &str) -> Option<Credential> {
+    let mut username = None;
+    let mut password = None;
+    for line in output.lines() {
+        match line.split_once('=') {
+            Some(("username", value)) => username = Some(value.to_string()),
+            Some(("password", value)) => password = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    Some(Credential {
+        username: username.unwrap_or_default(),
+        password: password?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_credential_reads_username_and_password() {
+        let parsed = parse_credential(
+            "protocol=https\nhost=company.atlassian.net\nusername=me@example.com\npassword=s3cr=t\n",
+        );
+        assert_eq!(
+            parsed,
+            Some(Credential {
+                username: "me@example.com".into(),
+                password: "s3cr=t".into(),
+            })
+        );
+        assert_eq!(parse_credential("protocol=https\nhost=example.com\n"), None);
+    }
+}
@@ -9,4 +9,9 @@ pub const DEFAULT_CODE_DIR_FALLBACK: &str = "./code";
 pub const DEFAULT_TICKETS_DIR_FALLBACK: &str = "./tickets";
 pub const DEFAULT_RELEASE_OWNER: &str = "armaan-v924";
 pub const DEFAULT_RELEASE_REPO: &str = "worktree-manager";
+pub const DEFAULT_MAX_PARALLEL_JOBS: usize = 4;
+pub const DEFAULT_REMOTE: &str = "origin";
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 300;
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{prefix}/{ticket}-{slug}";
 pub const UPDATE_USER_AGENT: &str = concat!("tix/", env!("CARGO_PKG_VERSION"));
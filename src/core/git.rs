@@ -1,17 +1,27 @@
 //! Git helpers built on `git2` for worktree management and safety checks.
 
-use anyhow::{Context, Result};
+use crate::core::defaults;
+use anyhow::{Context, Result, bail};
 use git2::build::CheckoutBuilder;
 use git2::{
-    BranchType, Commit, Cred, RemoteCallbacks, Repository, StatusOptions, WorktreeAddOptions,
+    BranchType, Commit, Cred, ErrorCode, RebaseOptions, RemoteCallbacks, Repository,
+    RepositoryState, ResetType, StatusOptions, WorktreeAddOptions,
 };
 use log::{debug, warn};
+use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 /// Return `true` if the repository at `repo_path` has no modified/staged/untracked files.
+#[allow(dead_code)]
 pub fn is_clean(repo_path: &Path) -> Result<bool> {
+    is_clean_ignoring(repo_path, &[])
+}
+
+/// Like `is_clean`, but untracked files listed in `ignored` (relative paths) do not count.
+/// Used for files carried into a worktree with `carry_files`.
+pub fn is_clean_ignoring(repo_path: &Path, ignored: &[PathBuf]) -> Result<bool> {
     // Open the repo
     let repo =
         Repository::open(repo_path).context("Failed to open repository to check the status")?;
@@ -19,23 +29,94 @@ pub fn is_clean(repo_path: &Path) -> Result<bool> {
     // Configure status options (include untracked, exclude ignored)
     let mut options = StatusOptions::new();
     options.include_untracked(true);
+    // Carried files may live in untracked directories, so list those file by file.
+    options.recurse_untracked_dirs(!ignored.is_empty());
 
     let statuses = repo
         .statuses(Some(&mut options))
         .context("Failed to read repository status.")?;
 
-    // If there are 0 entries in the status list, it's clean.
-    Ok(statuses.is_empty())
+    // Clean when every remaining entry is an untracked file we were told to ignore.
+    Ok(statuses.iter().all(|entry| {
+        entry.status() == git2::Status::WT_NEW
+            && entry
+                .path()
+                .is_some_and(|path| ignored.iter().any(|i| i == Path::new(path)))
+    }))
+}
+
+/// List untracked and ignored files in the checkout at `repo_path` matching the pathspec
+/// `patterns` (git glob syntax), as paths relative to the checkout root.
+pub fn untracked_files_matching(repo_path: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(true)
+        .recurse_untracked_dirs(true)
+        .recurse_ignored_dirs(true);
+    for pattern in patterns {
+        options.pathspec(pattern);
+    }
+
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .context("Failed to read repository status.")?;
+    let mut files: Vec<PathBuf> = statuses
+        .iter()
+        .filter(|entry| {
+            entry
+                .status()
+                .intersects(git2::Status::WT_NEW | git2::Status::IGNORED)
+        })
+        .filter_map(|entry| entry.path().map(PathBuf::from))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Per-repository remote settings (`remote`, `base_branch` and `fetch_refspecs` in config).
+#[derive(Debug, Clone, Copy)]
+pub struct RemoteOptions<'a> {
+    /// Remote name (defaults to `origin`).
+    pub remote: &'a str,
+    /// Branch new ticket branches start from, overriding the remote's default branch.
+    pub base_branch: Option<&'a str>,
+    /// Refspecs to fetch; empty means every branch (`refs/heads/*`).
+    pub fetch_refspecs: &'a [String],
+}
+
+impl Default for RemoteOptions<'_> {
+    fn default() -> Self {
+        RemoteOptions {
+            remote: defaults::DEFAULT_REMOTE,
+            base_branch: None,
+            fetch_refspecs: &[],
+        }
+    }
+}
+
+/// Outcome of `create_worktree`, used by callers that need to undo it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatedWorktree {
+    /// Metadata name of the new worktree (under `.git/worktrees/`).
+    pub worktree_name: String,
+    /// `true` when the local branch did not exist and was created for this worktree.
+    pub branch_created: bool,
 }
 
 /// Create a git worktree at `target_path`, using `branch_name`, optionally created from `base_ref`.
+///
+/// Without `base_ref`, new branches start from the configured base branch or the remote default.
 pub fn create_worktree(
     repo_path: &Path,
     target_path: &Path,
     branch_name: &str,
     base_ref: Option<&str>,
-) -> Result<()> {
+    remote: &RemoteOptions,
+) -> Result<CreatedWorktree> {
     let repo = Repository::open(repo_path).context("Failed to open source repository")?;
+    let branch_created = repo.find_branch(branch_name, BranchType::Local).is_err();
 
     // 1. Resolve the branch
     let branch = match repo.find_branch(branch_name, git2::BranchType::Local) {
@@ -46,7 +127,7 @@ pub fn create_worktree(
         }
         Err(_) => {
             // Case B: Branch does not exist. Prefer a matching remote branch if available.
-            if let Some(remote_branch) = find_remote_branch(&repo, branch_name) {
+            if let Some(remote_branch) = find_remote_branch(&repo, remote.remote, branch_name) {
                 debug!(
                     "Found remote branch '{}' for '{}'; creating local tracking branch",
                     remote_branch, branch_name
@@ -68,7 +149,7 @@ pub fn create_worktree(
                 // Create it from base.
                 debug!("Branch '{}' not found. Creating from base...", branch_name);
 
-                let default = resolve_default_branch(&repo);
+                let default = resolve_default_branch(&repo, remote);
                 let base = base_ref.or(default.as_deref());
                 if base_ref.is_none() && default.is_none() {
                     warn!(
@@ -93,17 +174,28 @@ pub fn create_worktree(
 
     let worktree_name = branch_name.replace('/', "_");
 
-    repo.worktree(
+    if let Err(e) = repo.worktree(
         &worktree_name, // metadata name for the worktree
         target_path,    // disk path
-        Some(&mut worktree_options),
-    )
-    .context("Failed to create a worktree")?;
+        Some(&worktree_options),
+    ) {
+        // Don't leave behind a branch we created for a worktree that never materialized.
+        if branch_created
+            && let Ok(mut created) = repo.find_branch(branch_name, BranchType::Local)
+            && let Err(del) = created.delete()
+        {
+            warn!("Failed to delete branch '{}': {}", branch_name, del);
+        }
+        return Err(anyhow::Error::new(e).context("Failed to create a worktree"));
+    }
 
     // If the local branch has no upstream but a remote branch exists, set it.
-    ensure_upstream(&repo, branch_name);
+    ensure_upstream(&repo, remote.remote, branch_name);
 
-    Ok(())
+    Ok(CreatedWorktree {
+        worktree_name,
+        branch_created,
+    })
 }
 
 fn get_base_commit<'a>(repo: &'a Repository, base: Option<&str>) -> Result<Commit<'a>> {
@@ -146,6 +238,123 @@ pub fn remove_worktree(repo_path: &Path, worktree_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// A worktree registered in a repository's `.git/worktrees/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredWorktree {
+    /// Metadata name (directory under `.git/worktrees/`).
+    pub name: String,
+    /// Worktree directory the metadata points at.
+    pub path: PathBuf,
+}
+
+/// List the worktrees registered in the repository at `repo_path`.
+pub fn registered_worktrees(repo_path: &Path) -> Result<Vec<RegisteredWorktree>> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let names = repo.worktrees().context("Failed to list worktrees")?;
+    let mut worktrees = Vec::new();
+    for name in names.iter().flatten() {
+        match repo.find_worktree(name) {
+            Ok(worktree) => worktrees.push(RegisteredWorktree {
+                name: name.to_string(),
+                path: worktree.path().to_path_buf(),
+            }),
+            Err(e) => debug!("Skipping unreadable worktree '{}': {}", name, e),
+        }
+    }
+    Ok(worktrees)
+}
+
+/// True when `worktree_path` opens as a git worktree of the repository at `repo_path`.
+pub fn is_linked_worktree(repo_path: &Path, worktree_path: &Path) -> bool {
+    let (Ok(repo), Ok(worktree)) = (Repository::open(repo_path), Repository::open(worktree_path))
+    else {
+        return false;
+    };
+    worktree.is_worktree() && same_path(repo.commondir(), worktree.commondir())
+}
+
+/// Point the worktree metadata `worktree_name` and the directory `worktree_path` at each other
+/// again (what `git worktree repair` does), e.g. after the ticket directory was moved.
+pub fn relink_worktree(repo_path: &Path, worktree_name: &str, worktree_path: &Path) -> Result<()> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let admin_dir = repo.path().join("worktrees").join(worktree_name);
+    if !admin_dir.is_dir() {
+        bail!(
+            "Worktree metadata '{}' does not exist in {:?}",
+            worktree_name,
+            repo_path
+        );
+    }
+    let worktree_path = worktree_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {:?}", worktree_path))?;
+    fs::write(
+        admin_dir.join("gitdir"),
+        format!("{}\n", worktree_path.join(".git").display()),
+    )
+    .context("Failed to update worktree metadata")?;
+    fs::write(
+        worktree_path.join(".git"),
+        format!("gitdir: {}\n", admin_dir.display()),
+    )
+    .with_context(|| format!("Failed to update {:?}", worktree_path.join(".git")))?;
+    // libgit2 records an absolute `commondir`, which goes stale when the repository moves.
+    let commondir = admin_dir.join("commondir");
+    if fs::read_to_string(&commondir).is_ok_and(|dir| Path::new(dir.trim()).is_absolute()) {
+        fs::write(&commondir, format!("{}\n", repo.path().display()))
+            .context("Failed to update worktree metadata")?;
+    }
+    Ok(())
+}
+
+/// True when the worktree metadata `worktree_name` and the directory `worktree_path` point at
+/// each other.
+pub fn worktree_linked(repo_path: &Path, worktree_name: &str, worktree_path: &Path) -> bool {
+    let registered = registered_worktrees(repo_path)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|worktree| worktree.name == worktree_name);
+    registered.is_some_and(|worktree| same_path(&worktree.path, worktree_path))
+        && is_linked_worktree(repo_path, worktree_path)
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Where `create_worktree` would start `branch_name` from, or `None` if the branch already exists.
+pub fn new_branch_start_point(
+    repo_path: &Path,
+    branch_name: &str,
+    base_ref: Option<&str>,
+    remote: &RemoteOptions,
+) -> Result<Option<String>> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    if repo.find_branch(branch_name, BranchType::Local).is_ok() {
+        return Ok(None);
+    }
+    let start = find_remote_branch(&repo, remote.remote, branch_name)
+        .or_else(|| base_ref.map(str::to_string))
+        .or_else(|| resolve_default_branch(&repo, remote))
+        .unwrap_or_else(|| "HEAD".to_string());
+    Ok(Some(short_ref_name(&start).to_string()))
+}
+
+/// Delete the local branch `branch_name` from the repository at `repo_path`.
+pub fn delete_local_branch(repo_path: &Path, branch_name: &str) -> Result<()> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let mut branch = repo
+        .find_branch(branch_name, BranchType::Local)
+        .with_context(|| format!("Branch '{}' not found", branch_name))?;
+    branch
+        .delete()
+        .with_context(|| format!("Failed to delete branch '{}'", branch_name))?;
+    Ok(())
+}
+
 /// Attempt to retrieve credentials using `git credential fill` command.
 /// This uses the same credential system as command-line git, which can access
 /// OS keychains and other credential stores that libgit2 might not be able to access directly.
@@ -184,11 +393,11 @@ fn get_credentials_via_git_command(url: &str) -> Option<(String, String)> {
     };
 
     // Write input to stdin
-    if let Some(mut stdin) = child.stdin.take() {
-        if stdin.write_all(input.as_bytes()).is_err() {
-            debug!("Failed to write to git credential fill stdin");
-            return None;
-        }
+    if let Some(mut stdin) = child.stdin.take()
+        && stdin.write_all(input.as_bytes()).is_err()
+    {
+        debug!("Failed to write to git credential fill stdin");
+        return None;
     }
 
     // Read output
@@ -327,15 +536,16 @@ fn create_git_callbacks<'a>() -> RemoteCallbacks<'a> {
     callbacks
 }
 
-/// Clone a repository to `target`.
+/// Clone a repository to `target`, naming the remote `remote_name`.
 ///
 /// Supports cloning both public and private repositories by using system git credentials.
 /// Authentication is handled automatically through SSH keys, credential helpers, or default credentials.
-pub fn clone_repo(url: &str, target: &Path) -> Result<()> {
+pub fn clone_repo(url: &str, target: &Path, remote_name: &str) -> Result<()> {
     let mut builder = git2::build::RepoBuilder::new();
     let mut fetch_options = git2::FetchOptions::new();
     fetch_options.remote_callbacks(create_git_callbacks());
     builder.fetch_options(fetch_options);
+    builder.remote_create(move |repo, _, url| repo.remote(remote_name, url));
 
     builder
         .clone(url, target)
@@ -343,26 +553,10 @@ pub fn clone_repo(url: &str, target: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Fetch from `remote_name` and fast-forward the current branch to its upstream if possible.
-pub fn fetch_and_fast_forward(repo_path: &Path, remote_name: &str) -> Result<()> {
+/// Fetch from the configured remote and fast-forward the current branch to its upstream if possible.
+pub fn fetch_and_fast_forward(repo_path: &Path, remote: &RemoteOptions) -> Result<()> {
     let repo = Repository::open(repo_path).context("Failed to open repository for fetch")?;
-
-    let mut remote = repo
-        .find_remote(remote_name)
-        .context(format!("Remote '{}' not found", remote_name))?;
-
-    debug!(
-        "Fetching all branches from '{}' in repo {:?}",
-        remote_name, repo_path
-    );
-    let refspec = format!("refs/heads/*:refs/remotes/{}/*", remote_name);
-
-    let mut fetch_options = git2::FetchOptions::new();
-    fetch_options.remote_callbacks(create_git_callbacks());
-
-    remote
-        .fetch(&[&refspec], Some(&mut fetch_options), None)
-        .context("Fetch failed")?;
+    fetch(&repo, remote, false)?;
 
     let head = match repo.head() {
         Ok(h) if h.is_branch() => h,
@@ -416,32 +610,84 @@ pub fn fetch_and_fast_forward(repo_path: &Path, remote_name: &str) -> Result<()>
     Ok(())
 }
 
-/// Resolve the default branch reference (e.g., origin/HEAD) to a revspec string.
-pub fn resolve_default_branch(repo: &Repository) -> Option<String> {
-    // Try remote HEAD first
-    if let Ok(remote) = repo.find_remote("origin") {
-        if let Ok(head) = remote.default_branch() {
-            if let Some(name) = head.as_str() {
-                return Some(name.to_string());
+/// Fetch from the configured remote, deleting remote-tracking branches whose remote branch is gone.
+pub fn fetch_pruned(repo_path: &Path, remote: &RemoteOptions) -> Result<()> {
+    let repo = Repository::open(repo_path).context("Failed to open repository for fetch")?;
+    fetch(&repo, remote, true)
+}
+
+fn fetch(repo: &Repository, remote: &RemoteOptions, prune: bool) -> Result<()> {
+    let remote_name = remote.remote;
+    let mut remote_handle = repo
+        .find_remote(remote_name)
+        .context(format!("Remote '{}' not found", remote_name))?;
+
+    let refspecs: Vec<String> = if remote.fetch_refspecs.is_empty() {
+        vec![format!("refs/heads/*:refs/remotes/{}/*", remote_name)]
+    } else {
+        remote.fetch_refspecs.to_vec()
+    };
+    debug!(
+        "Fetching {:?} from '{}' in repo {:?}",
+        refspecs,
+        remote_name,
+        repo.path()
+    );
+
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(create_git_callbacks());
+    if prune {
+        fetch_options.prune(git2::FetchPrune::On);
+    }
+
+    remote_handle
+        .fetch(&refspecs, Some(&mut fetch_options), None)
+        .context("Fetch failed")?;
+    Ok(())
+}
+
+/// Resolve the base branch reference to a revspec string: the configured `base_branch`
+/// (remote-tracking copy preferred), then the remote's HEAD, then the local HEAD.
+pub fn resolve_default_branch(repo: &Repository, options: &RemoteOptions) -> Option<String> {
+    if let Some(base) = options.base_branch {
+        let tracking = format!("refs/remotes/{}/{}", options.remote, base);
+        let local = format!("refs/heads/{}", base);
+        for candidate in [tracking, local] {
+            if repo.find_reference(&candidate).is_ok() {
+                return Some(candidate);
             }
         }
+        warn!(
+            "Configured base_branch '{}' not found locally or on '{}'; using the default branch",
+            base, options.remote
+        );
+    }
+
+    // Try remote HEAD first
+    if let Ok(remote) = repo.find_remote(options.remote)
+        && let Ok(head) = remote.default_branch()
+        && let Some(name) = head.as_str()
+    {
+        return Some(name.to_string());
     }
 
     // Fallback to symbolic reference of HEAD
-    if let Ok(head) = repo.head() {
-        if let Ok(resolved) = head.resolve() {
-            if let Some(name) = resolved.name() {
-                warn!("Using HEAD ({}) as base; origin/HEAD not configured", name);
-                return Some(name.to_string());
-            }
-        }
+    if let Ok(head) = repo.head()
+        && let Ok(resolved) = head.resolve()
+        && let Some(name) = resolved.name()
+    {
+        warn!(
+            "Using HEAD ({}) as base; {}/HEAD not configured",
+            name, options.remote
+        );
+        return Some(name.to_string());
     }
 
     None
 }
 
-fn find_remote_branch(repo: &Repository, branch_name: &str) -> Option<String> {
-    let candidate = format!("origin/{}", branch_name);
+fn find_remote_branch(repo: &Repository, remote_name: &str, branch_name: &str) -> Option<String> {
+    let candidate = format!("{}/{}", remote_name, branch_name);
     if repo.find_branch(&candidate, BranchType::Remote).is_ok() {
         Some(candidate)
     } else {
@@ -449,8 +695,8 @@ fn find_remote_branch(repo: &Repository, branch_name: &str) -> Option<String> {
     }
 }
 
-fn ensure_upstream(repo: &Repository, branch_name: &str) {
-    let candidate = match find_remote_branch(repo, branch_name) {
+fn ensure_upstream(repo: &Repository, remote_name: &str, branch_name: &str) {
+    let candidate = match find_remote_branch(repo, remote_name, branch_name) {
         Some(name) => name,
         None => return,
     };
@@ -468,3 +714,739 @@ fn ensure_upstream(repo: &Repository, branch_name: &str) {
         );
     }
 }
+
+/// Commits ahead of and behind a reference branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Divergence {
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+/// Snapshot of a worktree's branch, tracking state, and working-tree changes.
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeStatus {
+    /// Checked-out branch (`None` when HEAD is detached).
+    pub branch: Option<String>,
+    /// Upstream tracking branch (e.g., `origin/feature/JIRA-1`).
+    pub upstream: Option<String>,
+    /// Divergence from the upstream branch, if one is configured.
+    pub upstream_divergence: Option<Divergence>,
+    /// Base branch the ticket branch is compared against (e.g., `main`).
+    pub base: Option<String>,
+    /// Divergence from the base branch, if it could be resolved.
+    pub base_divergence: Option<Divergence>,
+    /// Number of files with staged changes.
+    pub staged: usize,
+    /// Number of files with unstaged changes (including conflicts).
+    pub unstaged: usize,
+    /// Number of untracked files.
+    pub untracked: usize,
+}
+
+/// Inspect the worktree at `worktree_path`, comparing HEAD against its upstream and `base_ref`.
+pub fn worktree_status(worktree_path: &Path, base_ref: Option<&str>) -> Result<WorktreeStatus> {
+    let repo =
+        Repository::open(worktree_path).context("Failed to open worktree to read its status")?;
+    let mut status = WorktreeStatus::default();
+
+    let head_oid = match repo.head() {
+        Ok(head) => {
+            if head.is_branch() {
+                status.branch = head.shorthand().map(|s| s.to_string());
+            }
+            head.target()
+        }
+        Err(_) => None, // Unborn branch; nothing to compare yet
+    };
+
+    if let Some(branch_name) = status.branch.as_deref()
+        && let Ok(local) = repo.find_branch(branch_name, BranchType::Local)
+        && let Ok(upstream) = local.upstream()
+    {
+        status.upstream = upstream.name().ok().flatten().map(|s| s.to_string());
+        if let (Some(local_oid), Some(upstream_oid)) = (head_oid, upstream.get().target()) {
+            status.upstream_divergence = divergence(&repo, local_oid, upstream_oid);
+        }
+    }
+
+    if let Some(base) = base_ref {
+        status.base = Some(short_ref_name(base).to_string());
+        let base_oid = repo
+            .revparse_single(base)
+            .and_then(|obj| obj.peel_to_commit())
+            .map(|c| c.id());
+        match (head_oid, base_oid) {
+            (Some(local_oid), Ok(base_oid)) => {
+                status.base_divergence = divergence(&repo, local_oid, base_oid);
+            }
+            (_, Err(e)) => debug!("Could not resolve base '{}': {}", base, e),
+            _ => {}
+        }
+    }
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .context("Failed to read worktree status")?;
+    for entry in statuses.iter() {
+        let s = entry.status();
+        if s.is_wt_new() {
+            status.untracked += 1;
+            continue;
+        }
+        if s.is_index_new()
+            || s.is_index_modified()
+            || s.is_index_deleted()
+            || s.is_index_renamed()
+            || s.is_index_typechange()
+        {
+            status.staged += 1;
+        }
+        if s.is_wt_modified()
+            || s.is_wt_deleted()
+            || s.is_wt_renamed()
+            || s.is_wt_typechange()
+            || s.is_conflicted()
+        {
+            status.unstaged += 1;
+        }
+    }
+
+    Ok(status)
+}
+
+/// Resolve the base branch for the repository at `repo_path` (see `resolve_default_branch`).
+pub fn default_branch_for(repo_path: &Path, remote: &RemoteOptions) -> Option<String> {
+    let repo = Repository::open(repo_path).ok()?;
+    resolve_default_branch(&repo, remote)
+}
+
+/// Return the commit id `branch_name` points at, or `None` if the branch does not exist.
+pub fn branch_head(repo_path: &Path, branch_name: &str) -> Result<Option<String>> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    match repo.find_branch(branch_name, BranchType::Local) {
+        Ok(branch) => Ok(branch.get().target().map(|oid| oid.to_string())),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Recreate `branch_name` at commit `head` if it no longer exists. Returns `true` if created.
+pub fn restore_branch(repo_path: &Path, branch_name: &str, head: &str) -> Result<bool> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    if repo.find_branch(branch_name, BranchType::Local).is_ok() {
+        return Ok(false);
+    }
+    let oid = git2::Oid::from_str(head).with_context(|| format!("Invalid commit id '{}'", head))?;
+    let commit = repo
+        .find_commit(oid)
+        .with_context(|| format!("Commit {} no longer exists in {:?}", head, repo_path))?;
+    repo.branch(branch_name, &commit, false)
+        .with_context(|| format!("Failed to recreate branch '{}'", branch_name))?;
+    Ok(true)
+}
+
+/// Resolve the ref a ticket branch should be synced onto: the remote-tracking copy of the
+/// default branch when it exists, otherwise the local default branch.
+pub fn sync_base_ref(repo_path: &Path, remote: &RemoteOptions) -> Option<String> {
+    let repo = Repository::open(repo_path).ok()?;
+    let default = resolve_default_branch(&repo, remote)?;
+    if let Some(branch) = default.strip_prefix("refs/heads/") {
+        let tracking = format!("refs/remotes/{}/{}", remote.remote, branch);
+        if repo.find_reference(&tracking).is_ok() {
+            return Some(tracking);
+        }
+    }
+    Some(default)
+}
+
+/// Strip `refs/heads/` or `refs/remotes/` from a reference name for display.
+pub fn short_ref_name(name: &str) -> &str {
+    name.strip_prefix("refs/heads/")
+        .or_else(|| name.strip_prefix("refs/remotes/"))
+        .unwrap_or(name)
+}
+
+fn divergence(repo: &Repository, local: git2::Oid, other: git2::Oid) -> Option<Divergence> {
+    match repo.graph_ahead_behind(local, other) {
+        Ok((ahead, behind)) => Some(Divergence { ahead, behind }),
+        Err(e) => {
+            debug!("Failed to compute ahead/behind: {}", e);
+            None
+        }
+    }
+}
+
+/// Short description of a commit for user-facing listings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSummary {
+    /// Abbreviated commit id.
+    pub id: String,
+    /// First line of the commit message.
+    pub summary: String,
+}
+
+/// Commits on a branch that exist neither on its upstream nor on the base branch.
+#[derive(Debug, Clone, Default)]
+pub struct UnpublishedCommits {
+    /// Upstream tracking branch, if configured.
+    pub upstream: Option<String>,
+    /// Base branch the commits were checked against, if resolved.
+    pub base: Option<String>,
+    /// Commits at risk of being orphaned, newest first.
+    pub commits: Vec<CommitSummary>,
+}
+
+/// Find commits on `branch_name` that are not reachable from its upstream or from `base_ref`.
+///
+/// Returns an empty result when the branch does not exist. When neither an upstream nor a base
+/// can be resolved the check is skipped (with a warning), since every commit would be reported.
+pub fn unpublished_commits(
+    repo_path: &Path,
+    branch_name: &str,
+    base_ref: Option<&str>,
+) -> Result<UnpublishedCommits> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let mut result = UnpublishedCommits::default();
+
+    let local = match repo.find_branch(branch_name, BranchType::Local) {
+        Ok(b) => b,
+        Err(_) => {
+            debug!(
+                "Branch '{}' not found in {:?}; nothing to check",
+                branch_name, repo_path
+            );
+            return Ok(result);
+        }
+    };
+    let local_oid = local
+        .get()
+        .target()
+        .context("Branch reference had no target")?;
+
+    let mut walk = repo.revwalk().context("Failed to start revision walk")?;
+    walk.push(local_oid)?;
+
+    let mut hidden = false;
+    if let Ok(upstream) = local.upstream() {
+        result.upstream = upstream.name().ok().flatten().map(|s| s.to_string());
+        if let Some(oid) = upstream.get().target() {
+            walk.hide(oid)?;
+            hidden = true;
+        }
+    }
+    if let Some(base) = base_ref {
+        match repo.revparse_single(base).and_then(|o| o.peel_to_commit()) {
+            Ok(commit) => {
+                result.base = Some(short_ref_name(base).to_string());
+                walk.hide(commit.id())?;
+                hidden = true;
+            }
+            Err(e) => debug!("Could not resolve base '{}': {}", base, e),
+        }
+    }
+    if !hidden {
+        warn!(
+            "Could not resolve an upstream or base branch for '{}' in {:?}; skipping unpushed-commit check",
+            branch_name, repo_path
+        );
+        return Ok(result);
+    }
+
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let id = commit.as_object().short_id()?;
+        result.commits.push(CommitSummary {
+            id: id.as_str().unwrap_or_default().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+        });
+    }
+    Ok(result)
+}
+
+/// Why a ticket branch no longer carries work of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchRetirement {
+    /// The local branch no longer exists.
+    Deleted,
+    /// Every commit on the branch is reachable from this base branch.
+    Merged(String),
+    /// The branch tracked this remote branch (`upstream`), which has since been deleted.
+    /// `unpushed` counts commits on the branch that are not reachable from the base or from any
+    /// remote-tracking branch; such a branch must not be deleted.
+    RemoteDeleted { upstream: String, unpushed: usize },
+}
+
+/// Check whether `branch_name` has been merged into `base_ref` or deleted on its remote.
+///
+/// "Merged" means the same as for `is_merged`, so a branch without commits of its own counts as
+/// merged. Remote deletions are only visible after a pruning fetch (see `fetch_pruned`); they
+/// report how many commits would be lost with the branch.
+pub fn branch_retirement(
+    repo_path: &Path,
+    branch_name: &str,
+    base_ref: Option<&str>,
+) -> Result<Option<BranchRetirement>> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let Ok(local) = repo.find_branch(branch_name, BranchType::Local) else {
+        return Ok(Some(BranchRetirement::Deleted));
+    };
+    let head = local
+        .get()
+        .target()
+        .context("Branch reference had no target")?;
+
+    let refname = format!("refs/heads/{}", branch_name);
+    if let Some(base) = base_ref {
+        match repo.revparse_single(base).and_then(|o| o.peel_to_commit()) {
+            Ok(commit) => {
+                if is_reachable_from(&repo, head, commit.id())? {
+                    return Ok(Some(BranchRetirement::Merged(
+                        short_ref_name(base).to_string(),
+                    )));
+                }
+            }
+            Err(e) => debug!("Could not resolve base '{}': {}", base, e),
+        }
+    }
+
+    // The upstream is recorded in config even after its remote-tracking ref was pruned.
+    if let Ok(upstream) = repo.branch_upstream_name(&refname)
+        && let Some(upstream) = upstream.as_str()
+        && repo.find_reference(upstream).is_err()
+    {
+        let mut walk = repo.revwalk()?;
+        walk.push(head)?;
+        walk.hide_glob("refs/remotes/*")?;
+        if let Some(base) = base_ref
+            && let Ok(commit) = repo.revparse_single(base).and_then(|o| o.peel_to_commit())
+        {
+            walk.hide(commit.id())?;
+        }
+        return Ok(Some(BranchRetirement::RemoteDeleted {
+            upstream: short_ref_name(upstream).to_string(),
+            unpushed: walk.count(),
+        }));
+    }
+    Ok(None)
+}
+
+/// Result of updating a ticket branch onto its base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// The branch already contains the base.
+    UpToDate,
+    /// The branch had no commits of its own and was moved to the base.
+    FastForwarded,
+    /// A merge commit was created.
+    Merged,
+    /// The branch's commits were replayed on top of the base (count of commits applied).
+    Rebased(usize),
+    /// The update stopped on conflicts in the listed paths; the operation is left in progress.
+    Conflicts(Vec<String>),
+}
+
+/// Merge `base_ref` into the branch checked out in `worktree_path`.
+///
+/// On conflicts the merge is left in progress (`MERGE_HEAD` written) for manual resolution.
+pub fn merge_base_into(worktree_path: &Path, base_ref: &str) -> Result<SyncOutcome> {
+    let repo = Repository::open(worktree_path).context("Failed to open worktree")?;
+    let base = repo
+        .revparse_single(base_ref)
+        .and_then(|o| o.peel_to_commit())
+        .with_context(|| format!("Could not resolve base '{}'", base_ref))?;
+    let annotated = repo.find_annotated_commit(base.id())?;
+    let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+
+    if analysis.is_up_to_date() {
+        return Ok(SyncOutcome::UpToDate);
+    }
+    if analysis.is_fast_forward() {
+        fast_forward_head(&repo, base.id())?;
+        return Ok(SyncOutcome::FastForwarded);
+    }
+
+    repo.merge(&[&annotated], None, Some(CheckoutBuilder::new().safe()))
+        .context("Merge failed")?;
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Ok(SyncOutcome::Conflicts(conflict_paths(&index)?));
+    }
+
+    let signature = repo
+        .signature()
+        .context("Could not determine commit author; configure user.name and user.email")?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let message = format!(
+        "Merge {} into {}",
+        short_ref_name(base_ref),
+        repo.head()?.shorthand().unwrap_or("HEAD")
+    );
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &[&head, &base],
+    )
+    .context("Failed to create merge commit")?;
+    repo.cleanup_state()?;
+    Ok(SyncOutcome::Merged)
+}
+
+/// Rebase the branch checked out in `worktree_path` onto `base_ref`.
+///
+/// On conflicts the rebase is left in progress so it can be finished with `git rebase --continue`.
+pub fn rebase_onto_base(worktree_path: &Path, base_ref: &str) -> Result<SyncOutcome> {
+    let repo = Repository::open(worktree_path).context("Failed to open worktree")?;
+    let base = repo
+        .revparse_single(base_ref)
+        .and_then(|o| o.peel_to_commit())
+        .with_context(|| format!("Could not resolve base '{}'", base_ref))?;
+    let upstream = repo.find_annotated_commit(base.id())?;
+    let (analysis, _) = repo.merge_analysis(&[&upstream])?;
+
+    if analysis.is_up_to_date() {
+        return Ok(SyncOutcome::UpToDate);
+    }
+    if analysis.is_fast_forward() {
+        fast_forward_head(&repo, base.id())?;
+        return Ok(SyncOutcome::FastForwarded);
+    }
+
+    let signature = repo
+        .signature()
+        .context("Could not determine commit author; configure user.name and user.email")?;
+    let branch = repo.reference_to_annotated_commit(&repo.head()?)?;
+    let mut rebase = repo
+        .rebase(
+            Some(&branch),
+            Some(&upstream),
+            None,
+            Some(&mut RebaseOptions::new()),
+        )
+        .context("Failed to start rebase")?;
+
+    let mut applied = 0;
+    while let Some(op) = rebase.next() {
+        op.context("Failed to apply rebase step")?;
+        let index = repo.index()?;
+        if index.has_conflicts() {
+            return Ok(SyncOutcome::Conflicts(conflict_paths(&index)?));
+        }
+        match rebase.commit(None, &signature, None) {
+            Ok(_) => applied += 1,
+            // The change is already in the base; nothing to commit for this step.
+            Err(e) if e.code() == ErrorCode::Applied => {}
+            Err(e) => return Err(anyhow::Error::new(e).context("Failed to commit rebase step")),
+        }
+    }
+    rebase
+        .finish(Some(&signature))
+        .context("Failed to finish rebase")?;
+    Ok(SyncOutcome::Rebased(applied))
+}
+
+/// Return `true` if a merge, rebase, or similar operation is in progress in `worktree_path`.
+pub fn operation_in_progress(worktree_path: &Path) -> Result<bool> {
+    let repo = Repository::open(worktree_path).context("Failed to open worktree")?;
+    Ok(repo.state() != RepositoryState::Clean)
+}
+
+/// Abort an in-progress rebase or merge in `worktree_path`. Returns `false` if none was active.
+pub fn abort_in_progress(worktree_path: &Path) -> Result<bool> {
+    let repo = Repository::open(worktree_path).context("Failed to open worktree")?;
+    match repo.state() {
+        RepositoryState::Clean => Ok(false),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => {
+            let mut rebase = repo.open_rebase(None).context("Failed to open rebase")?;
+            rebase.abort().context("Failed to abort rebase")?;
+            Ok(true)
+        }
+        _ => {
+            let head = repo.head()?.peel_to_commit()?;
+            repo.reset(head.as_object(), ResetType::Hard, None)
+                .context("Failed to reset worktree")?;
+            repo.cleanup_state()?;
+            Ok(true)
+        }
+    }
+}
+
+fn fast_forward_head(repo: &Repository, target: git2::Oid) -> Result<()> {
+    let head = repo.head()?;
+    let head_name = head
+        .name()
+        .context("HEAD has no reference name")?
+        .to_string();
+    // Check out against the old HEAD before moving the branch, so new files are written.
+    repo.checkout_tree(
+        repo.find_commit(target)?.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    )
+    .context("Failed to check out fast-forwarded HEAD")?;
+    repo.find_reference(&head_name)?
+        .set_target(target, "tix sync: fast-forward")?;
+    Ok(())
+}
+
+fn conflict_paths(index: &git2::Index) -> Result<Vec<String>> {
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    Ok(paths)
+}
+
+/// Return `true` when the reference `name` (e.g., `refs/heads/main`) exists.
+pub fn has_reference(repo_path: &Path, name: &str) -> bool {
+    Repository::open(repo_path).is_ok_and(|repo| repo.find_reference(name).is_ok())
+}
+
+/// Return `true` when every commit on `reference` (e.g., `refs/heads/feature/JIRA-1`) is
+/// reachable from `base_ref`.
+pub fn is_merged(repo_path: &Path, reference: &str, base_ref: &str) -> Result<bool> {
+    let repo = Repository::open(repo_path).context("Failed to open repository")?;
+    let head = repo
+        .refname_to_id(reference)
+        .with_context(|| format!("Reference '{}' not found", reference))?;
+    let base = repo
+        .revparse_single(base_ref)
+        .and_then(|o| o.peel_to_commit())
+        .with_context(|| format!("Could not resolve base '{}'", base_ref))?;
+    is_reachable_from(&repo, head, base.id())
+}
+
+/// Whether `commit` is reachable from `base`: the single definition of "merged" shared by
+/// `destroy` (`is_merged`, `delete_remote_branch`) and `gc` (`branch_retirement`).
+fn is_reachable_from(repo: &Repository, commit: git2::Oid, base: git2::Oid) -> Result<bool> {
+    Ok(commit == base || repo.graph_descendant_of(base, commit)?)
+}
+
+/// Result of deleting a ticket branch on its remote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteBranchDeletion {
+    /// The remote branch was deleted.
+    Deleted,
+    /// The remote has no branch with that name.
+    NotFound,
+    /// The remote branch has commits that are not reachable from the base; it was kept.
+    NotMerged,
+}
+
+/// Delete `branch_name` on `remote_name` along with its remote-tracking ref.
+///
+/// With `merged_into`, the branch is only deleted when the remote tip is reachable from that
+/// base; a tip that was never fetched counts as not merged.
+pub fn delete_remote_branch(
+    repo_path: &Path,
+    remote_name: &str,
+    branch_name: &str,
+    merged_into: Option<&str>,
+) -> Result<RemoteBranchDeletion> {
+    let repo = Repository::open(repo_path).context("Failed to open repository for push")?;
+    let remote_ref = format!("refs/heads/{}", branch_name);
+    let mut remote = repo
+        .find_remote(remote_name)
+        .context(format!("Remote '{}' not found", remote_name))?;
+    remote
+        .connect_auth(git2::Direction::Push, Some(create_git_callbacks()), None)
+        .with_context(|| format!("Failed to connect to remote '{}'", remote_name))?;
+    let remote_tip = remote
+        .list()?
+        .iter()
+        .find(|head| head.name() == remote_ref)
+        .map(|head| head.oid());
+    remote.disconnect()?;
+
+    let tracking_ref = format!("refs/remotes/{}/{}", remote_name, branch_name);
+    let Some(tip) = remote_tip else {
+        if let Ok(mut stale) = repo.find_reference(&tracking_ref) {
+            stale
+                .delete()
+                .context("Failed to delete remote-tracking ref")?;
+        }
+        return Ok(RemoteBranchDeletion::NotFound);
+    };
+    if let Some(base) = merged_into {
+        let base = repo
+            .revparse_single(base)
+            .and_then(|o| o.peel_to_commit())
+            .with_context(|| format!("Could not resolve base '{}'", base))?;
+        if !is_reachable_from(&repo, tip, base.id()).unwrap_or(false) {
+            return Ok(RemoteBranchDeletion::NotMerged);
+        }
+    }
+
+    let refspec = format!(":{}", remote_ref);
+    let rejection = std::cell::RefCell::new(None);
+    {
+        let mut callbacks = create_git_callbacks();
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(message) = status {
+                *rejection.borrow_mut() = Some(format!("{}: {}", refname, message));
+            }
+            Ok(())
+        });
+        let mut push_options = git2::PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote
+            .push(&[&refspec], Some(&mut push_options))
+            .with_context(|| format!("Deleting remote branch '{}' failed", branch_name))?;
+    }
+    if let Some(message) = rejection.into_inner() {
+        bail!("Remote rejected push: {}", message);
+    }
+    if let Ok(mut tracking) = repo.find_reference(&tracking_ref) {
+        tracking
+            .delete()
+            .context("Failed to delete remote-tracking ref")?;
+    }
+    Ok(RemoteBranchDeletion::Deleted)
+}
+
+/// Result of pushing a ticket branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// The remote branch already points at the local commit.
+    UpToDate,
+    /// The branch does not exist remotely and has no commits beyond the base.
+    NoCommits,
+    /// The branch was created on the remote.
+    Created,
+    /// The remote branch was fast-forwarded.
+    Updated,
+    /// The remote branch was overwritten (`--force-with-lease`).
+    Forced,
+}
+
+/// Push `branch_name` to `remote_name` and set it as the branch's upstream.
+///
+/// Non-fast-forward updates are refused unless `force_with_lease` is set, in which case the
+/// remote branch must still match our remote-tracking ref (i.e. nobody pushed since we fetched).
+/// A branch with no commits beyond `base_ref` is not created on the remote.
+pub fn push_branch(
+    repo_path: &Path,
+    remote_name: &str,
+    branch_name: &str,
+    base_ref: Option<&str>,
+    force_with_lease: bool,
+) -> Result<PushOutcome> {
+    let repo = Repository::open(repo_path).context("Failed to open repository for push")?;
+    let local_ref = format!("refs/heads/{}", branch_name);
+    let local_oid = repo
+        .refname_to_id(&local_ref)
+        .with_context(|| format!("Branch '{}' not found", branch_name))?;
+
+    let mut remote = repo
+        .find_remote(remote_name)
+        .context(format!("Remote '{}' not found", remote_name))?;
+    remote
+        .connect_auth(git2::Direction::Push, Some(create_git_callbacks()), None)
+        .with_context(|| format!("Failed to connect to remote '{}'", remote_name))?;
+    let remote_tip = remote
+        .list()?
+        .iter()
+        .find(|head| head.name() == local_ref)
+        .map(|head| head.oid());
+    remote.disconnect()?;
+    debug!(
+        "Push {}: local {}, remote {:?}",
+        branch_name, local_oid, remote_tip
+    );
+
+    let tracking_ref = format!("refs/remotes/{}/{}", remote_name, branch_name);
+    let outcome = match remote_tip {
+        Some(tip) if tip == local_oid => {
+            set_upstream(&repo, remote_name, branch_name, local_oid)?;
+            return Ok(PushOutcome::UpToDate);
+        }
+        Some(tip) => {
+            let fast_forward = repo.graph_descendant_of(local_oid, tip).unwrap_or(false);
+            if fast_forward {
+                PushOutcome::Updated
+            } else if !force_with_lease {
+                bail!(
+                    "Remote branch '{}' has diverged; sync first or re-run with --force-with-lease",
+                    branch_name
+                );
+            } else {
+                let expected = repo.refname_to_id(&tracking_ref).ok();
+                if expected != Some(tip) {
+                    bail!(
+                        "Remote branch '{}' changed since the last fetch (expected {}, found {}); refusing to overwrite",
+                        branch_name,
+                        expected
+                            .map(|o| o.to_string())
+                            .unwrap_or_else(|| "nothing".into()),
+                        tip
+                    );
+                }
+                PushOutcome::Forced
+            }
+        }
+        None => {
+            let base = base_ref.and_then(|b| repo.revparse_single(b).ok()?.peel_to_commit().ok());
+            if let Some(base) = base
+                && (base.id() == local_oid
+                    || repo
+                        .graph_descendant_of(base.id(), local_oid)
+                        .unwrap_or(false))
+            {
+                return Ok(PushOutcome::NoCommits);
+            }
+            PushOutcome::Created
+        }
+    };
+
+    let refspec = if outcome == PushOutcome::Forced {
+        format!("+{}:{}", local_ref, local_ref)
+    } else {
+        format!("{}:{}", local_ref, local_ref)
+    };
+    let rejection = std::cell::RefCell::new(None);
+    {
+        let mut callbacks = create_git_callbacks();
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(message) = status {
+                *rejection.borrow_mut() = Some(format!("{}: {}", refname, message));
+            }
+            Ok(())
+        });
+        let mut push_options = git2::PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote
+            .push(&[&refspec], Some(&mut push_options))
+            .with_context(|| format!("Push of '{}' failed", branch_name))?;
+    }
+    if let Some(message) = rejection.into_inner() {
+        bail!("Remote rejected push: {}", message);
+    }
+
+    set_upstream(&repo, remote_name, branch_name, local_oid)?;
+    Ok(outcome)
+}
+
+fn set_upstream(
+    repo: &Repository,
+    remote_name: &str,
+    branch_name: &str,
+    oid: git2::Oid,
+) -> Result<()> {
+    let tracking_ref = format!("refs/remotes/{}/{}", remote_name, branch_name);
+    repo.reference(&tracking_ref, oid, true, "tix push: update tracking ref")
+        .context("Failed to update remote-tracking ref")?;
+    let mut local = repo.find_branch(branch_name, BranchType::Local)?;
+    local
+        .set_upstream(Some(&format!("{}/{}", remote_name, branch_name)))
+        .context("Failed to set upstream")?;
+    Ok(())
+}
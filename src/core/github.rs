@@ -0,0 +1,239 @@
+//! Minimal GitHub REST client shared by the GitHub Issues tracker and `tix pr`.
+//!
+//! The token comes from `TIX_GITHUB_TOKEN` or `GITHUB_TOKEN`, falling back to the password
+//! `git credential fill` returns for the GitHub host.
+
+use crate::core::config::Config;
+use crate::core::credentials;
+use crate::core::http;
+use anyhow::{Result, anyhow, bail};
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Environment variables checked for a GitHub token, in order.
+pub const TOKEN_ENVS: [&str; 2] = ["TIX_GITHUB_TOKEN", "GITHUB_TOKEN"];
+
+/// Client for the GitHub (or GitHub Enterprise) REST API.
+pub struct GitHubClient {
+    api_url: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+}
+
+impl GitHubClient {
+    /// Client for `api_url`, authenticating as the user of the GitHub site at `web_url`.
+    pub fn new(api_url: &str, web_url: &str) -> Result<Self> {
+        let token = token(web_url)?;
+        if token.is_none() {
+            debug!("No GitHub token found; sending anonymous requests");
+        }
+        Ok(GitHubClient {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+            agent: http::agent_without_status_errors(),
+        })
+    }
+
+    /// Client for the configured `github_base_url`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::new(&api_url(config), &config.github_base_url)
+    }
+
+    /// GET `path` (relative to the API root, e.g. `/repos/o/r/issues/1`) and decode the JSON.
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.get_with_query(path, &[])
+    }
+
+    /// Like `get`, with `query` parameters percent-encoded into the URL.
+    pub fn get_with_query<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T> {
+        self.fetch(path, query)?
+            .ok_or_else(|| anyhow!("GitHub returned 404 for {}", path))
+    }
+
+    /// Like `get`, but returns `None` when GitHub answers 404.
+    pub fn get_optional<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        self.fetch(path, &[])
+    }
+
+    fn fetch<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<Option<T>> {
+        let url = format!("{}{}", self.api_url, path);
+        debug!("GET {} {:?}", url, query);
+        let mut request = self.agent.get(&url).query_pairs(query.iter().copied());
+        for (name, value) in self.headers() {
+            request = request.header(name, &value);
+        }
+        let response = request
+            .call()
+            .map_err(|e| anyhow!("GitHub request GET {} failed: {}", path, e))?;
+        if response.status().as_u16() == 404 {
+            return Ok(None);
+        }
+        read_response("GET", path, response).map(Some)
+    }
+
+    /// POST a JSON `body` to `path` and decode the JSON response.
+    pub fn post<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T> {
+        self.send("POST", path, body)
+    }
+
+    /// PATCH `path` with a JSON `body` and decode the JSON response.
+    pub fn patch<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T> {
+        self.send("PATCH", path, body)
+    }
+
+    fn send<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T> {
+        let url = format!("{}{}", self.api_url, path);
+        debug!("{} {}", method, url);
+        let mut request = match method {
+            "PATCH" => self.agent.patch(&url),
+            _ => self.agent.post(&url),
+        };
+        for (name, value) in self.headers() {
+            request = request.header(name, &value);
+        }
+        let response = request
+            .send_json(body)
+            .map_err(|e| anyhow!("GitHub request {} {} failed: {}", method, path, e))?;
+        read_response(method, path, response)
+    }
+
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            ("Accept", "application/vnd.github+json".to_string()),
+            ("User-Agent", http::USER_AGENT.to_string()),
+        ];
+        if let Some(token) = &self.token {
+            headers.push(("Authorization", format!("Bearer {}", token)));
+        }
+        headers
+    }
+}
+
+/// Decode a successful response, or turn GitHub's error document into a readable error.
+fn read_response<T: DeserializeOwned>(
+    method: &str,
+    path: &str,
+    response: ureq::http::Response<ureq::Body>,
+) -> Result<T> {
+    let status = response.status().as_u16();
+    let mut body = response.into_body();
+    if status >= 400 {
+        let error: ErrorResponse = body.read_json().unwrap_or(ErrorResponse {
+            message: String::new(),
+            errors: Vec::new(),
+        });
+        let mut details = vec![error.message];
+        details.extend(error.errors.iter().map(|e| match e.get("message") {
+            Some(serde_json::Value::String(message)) => message.clone(),
+            _ => e.to_string(),
+        }));
+        details.retain(|d| !d.is_empty());
+        let hint = if matches!(status, 401 | 403) {
+            format!(" (set {} or configure git credentials)", TOKEN_ENVS[0])
+        } else {
+            String::new()
+        };
+        bail!(
+            "GitHub rejected {} {} (HTTP {}): {}{}",
+            method,
+            path,
+            status,
+            details.join("; "),
+            hint
+        );
+    }
+    body.read_json()
+        .map_err(|e| anyhow!("Failed to parse GitHub response for {}: {}", path, e))
+}
+
+/// `owner/name` of a GitHub repository from its clone URL (`git@host:owner/name.git`,
+/// `https://host/owner/name`, `ssh://git@host/owner/name.git`).
+pub fn repo_slug(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let mut segments = path.rsplit('/').filter(|s| !s.is_empty());
+    let name = segments.next()?;
+    let owner = segments.next()?;
+    Some(format!("{}/{}", owner, name))
+}
+
+/// REST API root for a GitHub site: `https://api.github.com` for github.com, otherwise the
+/// GitHub Enterprise `<base>/api/v3`.
+pub fn api_url_for(web_url: &str) -> String {
+    let base = web_url.trim_end_matches('/');
+    if base == "https://github.com" || base == "http://github.com" {
+        "https://api.github.com".to_string()
+    } else {
+        format!("{}/api/v3", base)
+    }
+}
+
+/// REST API root for the configured `github_base_url`.
+pub fn api_url(config: &Config) -> String {
+    api_url_for(&config.github_base_url)
+}
+
+fn token(web_url: &str) -> Result<Option<String>> {
+    for name in TOKEN_ENVS {
+        if let Ok(token) = env::var(name)
+            && !token.is_empty()
+        {
+            return Ok(Some(token));
+        }
+    }
+    if web_url.is_empty() {
+        return Ok(None);
+    }
+    Ok(credentials::git_credential_fill(web_url)?.map(|c| c.password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_slug_parses_clone_urls() {
+        for url in [
+            "git@github.com:my-org/api.git",
+            "https://github.com/my-org/api",
+            "https://github.com/my-org/api.git/",
+            "ssh://git@git.example.com:2222/my-org/api.git",
+        ] {
+            assert_eq!(repo_slug(url).as_deref(), Some("my-org/api"), "{}", url);
+        }
+        assert_eq!(repo_slug("api"), None);
+        assert_eq!(repo_slug("https://github.com/api"), None);
+    }
+
+    #[test]
+    fn api_url_for_handles_enterprise_hosts() {
+        assert_eq!(api_url_for("https://github.com/"), "https://api.github.com");
+        assert_eq!(
+            api_url_for("https://git.example.com"),
+            "https://git.example.com/api/v3"
+        );
+    }
+}
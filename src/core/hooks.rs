@@ -0,0 +1,424 @@
+//! Lifecycle hooks (`[hooks]` and `[repositories.<alias>.hooks]` in config).
+//!
+//! Hooks are shell commands run inside a repo worktree with the ticket environment set. Their
+//! output is logged line by line (stdout at info, stderr at warn). Ticket templates'
+//! `post_create` commands run the same way in the ticket directory.
+
+use crate::core::config::{Config, HookDefinition, HookFailurePolicy, RepoDefinition};
+use crate::core::defaults;
+use crate::core::plugins::{repo_env, ticket_env};
+use crate::core::process::{relay_lines, shell_command};
+use anyhow::{Context, Result, bail};
+use log::{info, warn};
+use std::fmt;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running hook is checked for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long to keep relaying output after a hook exits; background processes it started may
+/// hold the pipes open indefinitely.
+const RELAY_GRACE: Duration = Duration::from_millis(500);
+
+/// Lifecycle events that can trigger a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// After `tix setup` creates a worktree.
+    PostSetup,
+    /// After `tix add` creates a worktree.
+    PostAdd,
+    /// Before `tix remove` deletes a worktree.
+    PreRemove,
+    /// Before `tix destroy` deletes the ticket.
+    PreDestroy,
+}
+
+impl HookEvent {
+    /// Every event, in config order.
+    pub const ALL: [HookEvent; 4] = [
+        HookEvent::PostSetup,
+        HookEvent::PostAdd,
+        HookEvent::PreRemove,
+        HookEvent::PreDestroy,
+    ];
+
+    /// Config key for the event (e.g., `post_setup`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::PostSetup => "post_setup",
+            HookEvent::PostAdd => "post_add",
+            HookEvent::PreRemove => "pre_remove",
+            HookEvent::PreDestroy => "pre_destroy",
+        }
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The worktree a hook runs in.
+#[derive(Debug, Clone, Copy)]
+pub struct HookTarget<'a> {
+    /// Repo alias.
+    pub alias: &'a str,
+    /// Worktree directory (the hook's working directory).
+    pub worktree: &'a Path,
+    /// Ticket root directory.
+    pub ticket_root: &'a Path,
+    /// Ticket id.
+    pub ticket_id: &'a str,
+}
+
+/// Run the global hook and then the repo's hook for `event` in `target`.
+///
+/// Failures of `warn` hooks are logged; the first failing `abort` hook returns an error.
+pub fn run(
+    event: HookEvent,
+    config: &Config,
+    repo: Option<&RepoDefinition>,
+    target: &HookTarget,
+) -> Result<()> {
+    let hooks = [Some(&config.hooks), repo.map(|r| &r.hooks)];
+    for hook in hooks.into_iter().flatten().filter_map(|h| h.get(event)) {
+        if let Err(e) = run_hook(event, hook, target) {
+            match hook.on_failure {
+                HookFailurePolicy::Warn => {
+                    warn!("[{}] {} hook failed: {:#}", target.alias, event, e)
+                }
+                HookFailurePolicy::Abort => {
+                    bail!("{} hook failed in '{}': {:#}", event, target.alias, e)
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Commands that `run` would execute for `event`: the global hook first, then the repo's.
+pub fn commands<'a>(
+    event: HookEvent,
+    config: &'a Config,
+    repo: Option<&'a RepoDefinition>,
+) -> Vec<&'a str> {
+    [Some(&config.hooks), repo.map(|r| &r.hooks)]
+        .into_iter()
+        .flatten()
+        .filter_map(|h| h.get(event))
+        .map(|hook| hook.command.as_str())
+        .collect()
+}
+
+fn run_hook(event: HookEvent, hook: &HookDefinition, target: &HookTarget) -> Result<()> {
+    let timeout = Duration::from_secs(
+        hook.timeout_secs
+            .unwrap_or(defaults::DEFAULT_HOOK_TIMEOUT_SECS),
+    );
+    info!(
+        "[{}] Running {} hook: {}",
+        target.alias, event, hook.command
+    );
+
+    let mut cmd = shell_command(&hook.command);
+    cmd.current_dir(target.worktree)
+        .envs(ticket_env(target.ticket_root, target.ticket_id))
+        .envs(repo_env(target.alias, target.worktree))
+        .env("TIX_HOOK", event.as_str());
+    run_with_timeout(
+        cmd,
+        &hook.command,
+        &format!("{}:{}", target.alias, event),
+        timeout,
+    )
+}
+
+/// Run a template's `post_create` commands in the ticket directory, stopping at the first failure.
+pub fn run_post_create(
+    template: &str,
+    commands: &[String],
+    ticket_root: &Path,
+    ticket_id: &str,
+) -> Result<()> {
+    let timeout = Duration::from_secs(defaults::DEFAULT_HOOK_TIMEOUT_SECS);
+    let label = format!("template:{}", template);
+    for command in commands {
+        info!("[{}] Running post_create command: {}", label, command);
+        let mut cmd = shell_command(command);
+        cmd.current_dir(ticket_root)
+            .envs(ticket_env(ticket_root, ticket_id))
+            .env("TIX_TEMPLATE", template);
+        if let Err(e) = run_with_timeout(cmd, command, &label, timeout) {
+            bail!("post_create command '{}' failed: {:#}", command, e);
+        }
+    }
+    Ok(())
+}
+
+/// Spawn `cmd`, relay its output under `[label]` and kill it (with everything it started) after
+/// `timeout`.
+fn run_with_timeout(mut cmd: Command, command: &str, label: &str, timeout: Duration) -> Result<()> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // Run the hook in its own process group so a timeout can kill the shell's children too.
+    // That takes it out of the terminal's foreground group, so Ctrl-C kills it by hand.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to start '{}'", command))?;
+    #[cfg(unix)]
+    let _forwarding = signals::forward_to_group(child.id());
+
+    // Relay threads are detached so a hook that leaves background processes holding the
+    // pipes open cannot block us past the timeout.
+    let stdout = child.stdout.take().map(|out| {
+        let label = label.to_string();
+        thread::spawn(move || relay_lines(out, |line| info!("[{}] {}", label, line)))
+    });
+    let stderr = child.stderr.take().map(|err| {
+        let label = label.to_string();
+        thread::spawn(move || relay_lines(err, |line| warn!("[{}] {}", label, line)))
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for hook")? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            bail!("timed out after {}s", timeout.as_secs());
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    // Let the relays drain what the hook wrote, but leave them behind if a background process
+    // keeps the pipes open.
+    let relays: Vec<_> = [stdout, stderr].into_iter().flatten().collect();
+    let grace = Instant::now() + RELAY_GRACE;
+    while relays.iter().any(|relay| !relay.is_finished()) && Instant::now() < grace {
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    if !status.success() {
+        match status.code() {
+            Some(code) => bail!("exited with code {}", code),
+            None => bail!("terminated ({})", status),
+        }
+    }
+    Ok(())
+}
+
+/// Kill the hook's process group (see `run_with_timeout`), falling back to the child alone.
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: `kill` has no memory-safety preconditions; a negative pid targets the group.
+        if unsafe { libc::kill(-pid, libc::SIGKILL) } == 0 {
+            return;
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Killing of the running hook's process group when tix gets SIGINT/SIGTERM.
+///
+/// Hooks run one at a time, so a single slot holds the group. The group is killed outright, as
+/// on timeout, because background jobs of `sh` ignore SIGINT. The handler only uses
+/// async-signal-safe calls: it kills the group, then lets tix die from the signal it got.
+#[cfg(unix)]
+mod signals {
+    use std::sync::Once;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    static HOOK_GROUP: AtomicI32 = AtomicI32::new(0);
+    static INSTALL: Once = Once::new();
+
+    /// Clears the forwarding target when the hook is done.
+    pub struct Forwarding;
+
+    impl Drop for Forwarding {
+        fn drop(&mut self) {
+            HOOK_GROUP.store(0, Ordering::SeqCst);
+        }
+    }
+
+    /// Kill the process group led by `pid` on SIGINT/SIGTERM until the guard is dropped.
+    pub fn forward_to_group(pid: u32) -> Forwarding {
+        INSTALL.call_once(|| {
+            for signal in [libc::SIGINT, libc::SIGTERM] {
+                // SAFETY: `forward` is an `extern "C" fn(c_int)` that only makes
+                // async-signal-safe calls.
+                unsafe {
+                    let previous = libc::signal(signal, forward as *const () as libc::sighandler_t);
+                    // Leave signals alone that tix was started with ignored (e.g. `nohup`).
+                    if previous == libc::SIG_IGN {
+                        libc::signal(signal, libc::SIG_IGN);
+                    }
+                }
+            }
+        });
+        HOOK_GROUP.store(i32::try_from(pid).unwrap_or(0), Ordering::SeqCst);
+        Forwarding
+    }
+
+    extern "C" fn forward(signal: libc::c_int) {
+        let group = HOOK_GROUP.load(Ordering::SeqCst);
+        // SAFETY: `kill`, `signal` and `raise` are async-signal-safe.
+        unsafe {
+            if group > 0 {
+                libc::kill(-group, libc::SIGKILL);
+            }
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::config::HooksConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn hook(
+        command: &str,
+        timeout_secs: Option<u64>,
+        on_failure: HookFailurePolicy,
+    ) -> HooksConfig {
+        HooksConfig {
+            post_add: Some(HookDefinition {
+                command: command.into(),
+                timeout_secs,
+                on_failure,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn target(dir: &Path) -> HookTarget<'_> {
+        HookTarget {
+            alias: "api",
+            worktree: dir,
+            ticket_root: dir,
+            ticket_id: "JIRA-1",
+        }
+    }
+
+    #[test]
+    fn runs_global_then_repo_hook_with_ticket_env() {
+        let dir = TempDir::new().unwrap();
+        let config = Config {
+            hooks: hook(
+                "echo \"global $TIX_TICKET_ID $TIX_HOOK\" >> hooks.log",
+                None,
+                HookFailurePolicy::Abort,
+            ),
+            ..Default::default()
+        };
+        let repo = RepoDefinition {
+            hooks: hook(
+                "echo \"repo $TIX_REPO_ALIAS\" >> hooks.log",
+                None,
+                HookFailurePolicy::Abort,
+            ),
+            ..Default::default()
+        };
+
+        run(
+            HookEvent::PostAdd,
+            &config,
+            Some(&repo),
+            &target(dir.path()),
+        )
+        .unwrap();
+        run(
+            HookEvent::PreRemove,
+            &config,
+            Some(&repo),
+            &target(dir.path()),
+        )
+        .unwrap();
+
+        let log = fs::read_to_string(dir.path().join("hooks.log")).unwrap();
+        assert_eq!(log, "global JIRA-1 post_add\nrepo api\n");
+    }
+
+    #[test]
+    fn failure_policy_decides_whether_to_abort() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config {
+            hooks: hook("exit 3", None, HookFailurePolicy::Warn),
+            ..Default::default()
+        };
+        assert!(run(HookEvent::PostAdd, &config, None, &target(dir.path())).is_ok());
+
+        config.hooks = hook("exit 3", None, HookFailurePolicy::Abort);
+        let err = run(HookEvent::PostAdd, &config, None, &target(dir.path())).unwrap_err();
+        assert!(format!("{:#}", err).contains("exited with code 3"));
+    }
+
+    #[test]
+    fn post_create_commands_run_in_ticket_root_and_stop_on_failure() {
+        let dir = TempDir::new().unwrap();
+        let commands = vec![
+            "echo \"$TIX_TEMPLATE $TIX_TICKET_ID\" > created.txt".to_string(),
+            "exit 1".to_string(),
+            "touch never.txt".to_string(),
+        ];
+        let err = run_post_create("api-change", &commands, dir.path(), "JIRA-1").unwrap_err();
+        assert!(format!("{:#}", err).contains("'exit 1' failed: exited with code 1"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("created.txt")).unwrap(),
+            "api-change JIRA-1\n"
+        );
+        assert!(!dir.path().join("never.txt").exists());
+    }
+
+    #[test]
+    fn hooks_are_killed_after_timeout() {
+        let dir = TempDir::new().unwrap();
+        let config = Config {
+            hooks: hook("sleep 5", Some(1), HookFailurePolicy::Abort),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let err = run(HookEvent::PostAdd, &config, None, &target(dir.path())).unwrap_err();
+        assert!(format!("{:#}", err).contains("timed out after 1s"));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn timeout_kills_processes_started_by_the_hook() {
+        let dir = TempDir::new().unwrap();
+        let config = Config {
+            hooks: hook(
+                "(sleep 2; touch late.txt) & wait",
+                Some(1),
+                HookFailurePolicy::Abort,
+            ),
+            ..Default::default()
+        };
+        assert!(run(HookEvent::PostAdd, &config, None, &target(dir.path())).is_err());
+        thread::sleep(Duration::from_millis(2500));
+        assert!(!dir.path().join("late.txt").exists());
+    }
+
+    #[test]
+    fn background_processes_holding_output_do_not_block() {
+        let dir = TempDir::new().unwrap();
+        let config = Config {
+            hooks: hook("sleep 5 & echo started", Some(30), HookFailurePolicy::Abort),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        run(HookEvent::PostAdd, &config, None, &target(dir.path())).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,29 @@
+//! Shared HTTP settings for REST clients.
+
+use crate::core::defaults;
+use std::time::Duration;
+
+/// `User-Agent` sent with every request.
+pub const USER_AGENT: &str = defaults::UPDATE_USER_AGENT;
+
+/// Agent with the default request timeout.
+pub fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(timeout()))
+        .build()
+        .into()
+}
+
+/// Like `agent`, but 4xx/5xx responses are returned instead of turned into errors, so callers
+/// can read the error body.
+pub fn agent_without_status_errors() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(timeout()))
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+fn timeout() -> Duration {
+    Duration::from_secs(defaults::DEFAULT_HTTP_TIMEOUT_SECS)
+}
@@ -1,7 +1,19 @@
+pub mod branch;
+pub mod carry;
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod credentials;
 pub mod defaults;
 pub mod git;
+pub mod github;
+pub mod hooks;
+pub mod http;
+pub mod output;
+pub mod parallel;
+pub mod plan;
 pub mod plugins;
+pub mod process;
 pub mod ticket;
+pub mod ticket_id;
+pub mod tracker;
@@ -0,0 +1,166 @@
+//! Machine-readable output shared by read commands (`list`, `info`, `status`).
+//!
+//! JSON documents always carry `schema_version`; bump it when a field is removed or
+//! changes meaning. Adding new fields is backwards compatible and does not require a bump.
+
+use crate::core::ticket::{
+    IssueDetails, PullRequestRef, StatusTransition, TicketMetadata, TicketStatus,
+};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the JSON documents emitted with `--format json`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Output format for read commands.
+pub enum OutputFormat {
+    /// Human-readable fixed-width table.
+    #[default]
+    Table,
+    /// Versioned JSON document.
+    Json,
+    /// Tab-separated values with a header row.
+    Tsv,
+}
+
+#[derive(Serialize, Debug)]
+/// Stable JSON representation of a ticket workspace.
+pub struct TicketRecord {
+    pub id: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub branch: String,
+    pub repos: Vec<String>,
+    pub repo_branches: BTreeMap<String, String>,
+    pub repo_worktrees: BTreeMap<String, String>,
+    /// Absolute path to the ticket workspace.
+    pub path: String,
+    /// Issue link when an issue tracker is configured.
+    pub link: Option<String>,
+    /// Lifecycle status.
+    pub status: TicketStatus,
+    /// Lifecycle status changes, oldest first.
+    pub status_history: Vec<StatusTransition>,
+    /// Issue details fetched from the tracker, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<IssueDetails>,
+    /// Pull requests opened with `tix pr create`, keyed by repo alias.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub pull_requests: BTreeMap<String, PullRequestRef>,
+}
+
+impl TicketRecord {
+    /// Build a record from ticket metadata, sorting collections for stable output.
+    pub fn new(metadata: &TicketMetadata, path: &Path, link: Option<String>) -> Self {
+        let mut repos = metadata.repos.clone();
+        repos.sort();
+        TicketRecord {
+            id: metadata.id.clone(),
+            description: metadata.description.clone(),
+            created_at: metadata.created_at.clone(),
+            branch: metadata.branch.clone(),
+            repos,
+            repo_branches: metadata
+                .repo_branches
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            repo_worktrees: metadata
+                .repo_worktrees
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            path: path.display().to_string(),
+            link,
+            status: metadata.status.unwrap_or_default(),
+            status_history: metadata.status_history.clone(),
+            issue: metadata.issue.clone(),
+            pull_requests: metadata
+                .pull_requests
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Document<'a, T: Serialize> {
+    schema_version: u32,
+    #[serde(flatten)]
+    body: &'a T,
+}
+
+/// Print `body` as a pretty JSON document tagged with the current schema version.
+pub fn print_json<T: Serialize>(body: &T) -> Result<()> {
+    let doc = Document {
+        schema_version: SCHEMA_VERSION,
+        body,
+    };
+    println!("{}", serde_json::to_string_pretty(&doc)?);
+    Ok(())
+}
+
+/// Print a header and rows as tab-separated values.
+pub fn print_tsv(header: &[&str], rows: &[Vec<String>]) {
+    println!("{}", header.join("\t"));
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| tsv_cell(c)).collect();
+        println!("{}", cells.join("\t"));
+    }
+}
+
+/// Replace characters that would break TSV framing.
+fn tsv_cell(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    #[test]
+    fn tsv_cell_strips_separators() {
+        assert_eq!(tsv_cell("a\tb\nc"), "a b c");
+    }
+
+    #[test]
+    fn document_includes_schema_version() {
+        #[derive(Serialize)]
+        struct Body {
+            tickets: Vec<String>,
+        }
+        let doc = Document {
+            schema_version: SCHEMA_VERSION,
+            body: &Body {
+                tickets: vec!["JIRA-1".into()],
+            },
+        };
+        let value: Value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["tickets"][0], "JIRA-1");
+    }
+
+    #[test]
+    fn ticket_record_sorts_repos() {
+        let metadata = TicketMetadata {
+            id: "JIRA-1".into(),
+            description: None,
+            created_at: "2024-01-01T00:00:00Z".into(),
+            branch: "feature/JIRA-1".into(),
+            repos: vec!["web".into(), "api".into()],
+            repo_branches: HashMap::new(),
+            repo_worktrees: HashMap::new(),
+            ..Default::default()
+        };
+        let record = TicketRecord::new(&metadata, Path::new("/tickets/JIRA-1"), None);
+        assert_eq!(record.repos, vec!["api".to_string(), "web".to_string()]);
+        assert_eq!(record.path, "/tickets/JIRA-1");
+    }
+}
@@ -0,0 +1,101 @@
+//! Bounded worker pool for running per-repository work concurrently.
+
+use crate::core::defaults;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Resolve the worker count: `--jobs` wins, then `max_parallel_jobs`, then the built-in default.
+/// Always returns at least 1.
+pub fn resolve_jobs(cli_jobs: Option<usize>, config_jobs: Option<usize>) -> usize {
+    cli_jobs
+        .or(config_jobs)
+        .unwrap_or(defaults::DEFAULT_MAX_PARALLEL_JOBS)
+        .max(1)
+}
+
+/// Run `task` over `items` on at most `jobs` threads and return the results in input order.
+///
+/// Every item is processed even if some tasks fail, so callers can report all failures at once.
+pub fn run_bounded<T, R, F>(items: &[T], jobs: usize, task: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = jobs.max(1).min(items.len());
+    if workers <= 1 {
+        return items.iter().map(&task).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::SeqCst);
+                    if idx >= items.len() {
+                        break;
+                    }
+                    let result = task(&items[idx]);
+                    results.lock().unwrap_or_else(|e| e.into_inner())[idx] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|r| r.expect("worker pool task did not produce a result"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_jobs, run_bounded};
+    use crate::core::defaults;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn resolve_jobs_prefers_cli_then_config() {
+        assert_eq!(resolve_jobs(Some(3), Some(8)), 3);
+        assert_eq!(resolve_jobs(None, Some(8)), 8);
+        assert_eq!(
+            resolve_jobs(None, None),
+            defaults::DEFAULT_MAX_PARALLEL_JOBS
+        );
+        assert_eq!(resolve_jobs(Some(0), None), 1);
+    }
+
+    #[test]
+    fn run_bounded_preserves_order_and_limits_concurrency() {
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<usize> = (0..12).collect();
+
+        let results = run_bounded(&items, 3, |n| {
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            active.fetch_sub(1, Ordering::SeqCst);
+            n * 2
+        });
+
+        assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn run_bounded_collects_every_failure() {
+        let items = vec!["a", "b", "c"];
+        let results: Vec<Result<(), String>> =
+            run_bounded(&items, 2, |s| Err(format!("{} failed", s)));
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 3);
+    }
+}
@@ -0,0 +1,176 @@
+//! Planned changes for the global `--dry-run` flag.
+//!
+//! Mutating commands work out what they are going to do before doing it. With `--dry-run` that
+//! plan is printed as a list of actions and nothing on disk or in git is touched.
+
+use crate::core::config::{Config, RepoDefinition};
+use crate::core::hooks::{self, HookEvent};
+use std::fmt;
+use std::path::PathBuf;
+
+/// One change a command would make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Create a directory.
+    CreateDir(PathBuf),
+    /// Delete a directory and everything in it.
+    RemoveDir(PathBuf),
+    /// Create or update a ticket's `.tix/info.toml`.
+    WriteMetadata(PathBuf),
+    /// Fetch an issue's details from the issue tracker.
+    FetchIssue { ticket: String, tracker: String },
+    /// Fetch the source repository and fast-forward its checked-out branch.
+    Fetch { alias: String, repo: PathBuf },
+    /// Clone a repository.
+    Clone {
+        alias: String,
+        url: String,
+        path: PathBuf,
+    },
+    /// Create a local branch starting at `from`.
+    CreateBranch {
+        alias: String,
+        branch: String,
+        from: String,
+    },
+    /// Add a worktree for `branch` at `path`.
+    CreateWorktree {
+        alias: String,
+        path: PathBuf,
+        branch: String,
+    },
+    /// Prune the worktree registration `name` from the source repository.
+    PruneWorktree { alias: String, name: String },
+    /// Delete a local branch.
+    DeleteBranch { alias: String, branch: String },
+    /// Delete a branch on the remote.
+    DeleteRemoteBranch {
+        alias: String,
+        remote: String,
+        branch: String,
+    },
+    /// Leave something in place that the command would otherwise change.
+    Keep {
+        alias: String,
+        what: String,
+        reason: String,
+    },
+    /// Run a lifecycle hook in a worktree.
+    RunHook {
+        alias: String,
+        event: HookEvent,
+        command: String,
+    },
+    /// Run a template's `post_create` command in the ticket directory.
+    RunCommand { command: String, dir: PathBuf },
+    /// Set a config key.
+    SetConfig { key: String, value: String },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::CreateDir(path) => write!(f, "create directory {:?}", path),
+            Action::RemoveDir(path) => write!(f, "delete directory {:?}", path),
+            Action::WriteMetadata(path) => write!(f, "write ticket metadata {:?}", path),
+            Action::FetchIssue { ticket, tracker } => {
+                write!(f, "fetch issue {} from {}", ticket, tracker)
+            }
+            Action::Fetch { alias, repo } => {
+                write!(f, "[{}] fetch and fast-forward {:?}", alias, repo)
+            }
+            Action::Clone { alias, url, path } => {
+                write!(f, "[{}] clone {} into {:?}", alias, url, path)
+            }
+            Action::CreateBranch {
+                alias,
+                branch,
+                from,
+            } => write!(f, "[{}] create branch '{}' from {}", alias, branch, from),
+            Action::CreateWorktree {
+                alias,
+                path,
+                branch,
+            } => write!(f, "[{}] create worktree {:?} on '{}'", alias, path, branch),
+            Action::PruneWorktree { alias, name } => {
+                write!(f, "[{}] prune worktree metadata '{}'", alias, name)
+            }
+            Action::DeleteBranch { alias, branch } => {
+                write!(f, "[{}] delete branch '{}'", alias, branch)
+            }
+            Action::DeleteRemoteBranch {
+                alias,
+                remote,
+                branch,
+            } => write!(f, "[{}] delete branch '{}' on {}", alias, branch, remote),
+            Action::Keep {
+                alias,
+                what,
+                reason,
+            } => write!(f, "[{}] keep {} ({})", alias, what, reason),
+            Action::RunHook {
+                alias,
+                event,
+                command,
+            } => write!(f, "[{}] run {} hook: {}", alias, event, command),
+            Action::RunCommand { command, dir } => write!(f, "run '{}' in {:?}", command, dir),
+            Action::SetConfig { key, value } => write!(f, "set {} = {}", key, value),
+        }
+    }
+}
+
+/// `RunHook` actions for the hooks that `event` would run in `alias`'s worktree.
+pub fn hook_actions(
+    event: HookEvent,
+    config: &Config,
+    repo: Option<&RepoDefinition>,
+    alias: &str,
+) -> Vec<Action> {
+    hooks::commands(event, config, repo)
+        .into_iter()
+        .map(|command| Action::RunHook {
+            alias: alias.to_string(),
+            event,
+            command: command.to_string(),
+        })
+        .collect()
+}
+
+/// Print the plan of a dry run to stdout.
+pub fn print(actions: &[Action]) {
+    if actions.is_empty() {
+        println!("Dry run: nothing to do.");
+        return;
+    }
+    println!("Dry run: the following changes would be made:");
+    for action in actions {
+        println!("  - {}", action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_describe_the_change() {
+        let create = Action::CreateBranch {
+            alias: "api".into(),
+            branch: "feature/JIRA-1".into(),
+            from: "origin/main".into(),
+        };
+        assert_eq!(
+            create.to_string(),
+            "[api] create branch 'feature/JIRA-1' from origin/main"
+        );
+        let keep = Action::Keep {
+            alias: "web".into(),
+            what: "branch 'feature/JIRA-1'".into(),
+            reason: "not merged into main".into(),
+        };
+        assert_eq!(
+            keep.to_string(),
+            "[web] keep branch 'feature/JIRA-1' (not merged into main)"
+        );
+    }
+}
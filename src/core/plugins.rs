@@ -3,10 +3,12 @@
 use crate::core::commands::common::locate_ticket_root;
 use crate::core::config::{Config, PluginDefinition, RepoDefinition};
 use crate::core::ticket::Ticket;
+use crate::core::ticket_id;
 use anyhow::{Context, Result, bail};
 use directories::ProjectDirs;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -78,6 +80,9 @@ pub fn run_plugin(name: &str, args: &[String]) -> Result<()> {
 
     let ticket_root = locate_ticket_root(None, &config)?;
     let ticket = Ticket::load(&ticket_root)?;
+    // Plugins commonly build paths from the id, so a tampered info.toml must not reach them.
+    ticket_id::ensure_safe(&ticket.metadata.id)
+        .with_context(|| format!("Refusing to run plugin '{}'", name))?;
     let plugin_cache_dir = plugin_cache_dir(name, true)?;
     let plugin_state_dir = plugin_state_dir(name, true)?;
     let plugin_ticket_state_dir = plugin_ticket_state_dir(&ticket_root, name, true)?;
@@ -118,10 +123,13 @@ pub fn run_plugin(name: &str, args: &[String]) -> Result<()> {
         .args(args)
         .current_dir(&ticket_root)
         .env("TIX_CONTEXT_PATH", &context_path)
-        .env("TIX_TICKET_ROOT", &ticket_root)
+        .envs(ticket_env(&ticket_root, &context.ticket.id))
         .env("TIX_PLUGIN_CACHE_DIR", &plugin_cache_dir)
         .env("TIX_PLUGIN_STATE_DIR", &plugin_state_dir)
         .env("TIX_PLUGIN_TICKET_STATE_DIR", &plugin_ticket_state_dir);
+    if let (Some(alias), Some(path)) = (&context.current_repo_alias, &context.current_repo_path) {
+        command.envs(repo_env(alias, path));
+    }
 
     let status = command
         .status()
@@ -134,6 +142,22 @@ pub fn run_plugin(name: &str, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Environment variables describing the ticket, shared by plugins and `tix exec`.
+pub fn ticket_env(ticket_root: &Path, ticket_id: &str) -> Vec<(&'static str, OsString)> {
+    vec![
+        ("TIX_TICKET_ROOT", ticket_root.as_os_str().to_os_string()),
+        ("TIX_TICKET_ID", OsString::from(ticket_id)),
+    ]
+}
+
+/// Environment variables describing a single repo worktree within a ticket.
+pub fn repo_env(alias: &str, worktree_path: &Path) -> Vec<(&'static str, OsString)> {
+    vec![
+        ("TIX_REPO_ALIAS", OsString::from(alias)),
+        ("TIX_REPO_PATH", worktree_path.as_os_str().to_os_string()),
+    ]
+}
+
 fn resolve_entrypoint(config_path: &Path, entrypoint: &Path) -> PathBuf {
     if entrypoint.is_absolute() {
         return entrypoint.to_path_buf();
@@ -415,6 +439,7 @@ mod tests {
             RepoDefinition {
                 url: "https://example.com/api".into(),
                 path: PathBuf::from("/code/api"),
+                ..Default::default()
             },
         );
         let ctx = PluginContext {
@@ -431,6 +456,7 @@ mod tests {
                 repos: vec!["api".into()],
                 repo_branches: HashMap::new(),
                 repo_worktrees: HashMap::new(),
+                ..Default::default()
             },
             config: Config {
                 branch_prefix: "feature".into(),
@@ -441,6 +467,7 @@ mod tests {
                 repositories: HashMap::new(),
                 plugins: HashMap::new(),
                 jira_base_url: None,
+                ..Default::default()
             },
             code_directory: PathBuf::from("/code"),
             tickets_directory: PathBuf::from("/tickets"),
@@ -523,6 +550,7 @@ mod tests {
             repos: vec!["api".into(), "web".into()],
             repo_branches: HashMap::new(),
             repo_worktrees: HashMap::new(),
+            ..Default::default()
         };
         let cwd = Path::new("/tickets/JIRA-1/api/src");
 
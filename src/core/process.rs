@@ -0,0 +1,26 @@
+//! Helpers for spawning child processes and relaying their output.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::Command;
+
+/// Build a process that runs `command` through the platform shell (`sh -c` / `cmd /C`).
+pub fn shell_command(command: &str) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut cmd = Command::new(shell);
+    cmd.arg(flag).arg(command);
+    cmd
+}
+
+/// Read `stream` line by line (lossy UTF-8, trailing `\r` removed) and pass each line to `emit`.
+pub fn relay_lines(stream: impl Read, emit: impl Fn(&str)) {
+    let reader = BufReader::new(stream);
+    for line in reader.split(b'\n') {
+        let Ok(line) = line else { break };
+        let text = String::from_utf8_lossy(&line);
+        emit(text.trim_end_matches('\r'));
+    }
+}
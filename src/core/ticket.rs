@@ -1,15 +1,58 @@
 //! Ticket metadata stamp stored inside each ticket workspace.
 
+use crate::core::config::{CarryMode, RepoDefinition};
+use crate::core::git::RemoteOptions;
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const STAMP_DIR: &str = ".tix";
 const METADATA_FILE: &str = "info.toml";
+const ARCHIVE_DIR: &str = ".archive";
+const ARCHIVE_FILE: &str = "archive.toml";
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+/// Where a ticket is in its lifecycle.
+pub enum TicketStatus {
+    Todo,
+    #[default]
+    InProgress,
+    Review,
+    Blocked,
+    Done,
+}
+
+impl fmt::Display for TicketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TicketStatus::Todo => "todo",
+            TicketStatus::InProgress => "in-progress",
+            TicketStatus::Review => "review",
+            TicketStatus::Blocked => "blocked",
+            TicketStatus::Done => "done",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+/// A recorded lifecycle status change.
+pub struct StatusTransition {
+    /// Previous status, if any.
+    #[serde(default)]
+    pub from: Option<TicketStatus>,
+    /// New status.
+    pub to: TicketStatus,
+    /// Transition timestamp (ISO 8601).
+    pub at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 /// Metadata written to `.tix/info.toml` inside a ticket workspace.
 pub struct TicketMetadata {
     /// Ticket identifier (e.g., `JIRA-123`).
@@ -31,6 +74,96 @@ pub struct TicketMetadata {
     /// Mapping of repo alias to sanitized worktree name.
     #[serde(default)]
     pub repo_worktrees: HashMap<String, String>,
+    /// Base branch per repo alias when the ticket template overrode the repo's `base_branch`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub repo_base_branches: HashMap<String, String>,
+    /// Ticket type chosen at setup (selects a `branch_templates` entry).
+    #[serde(default)]
+    pub ticket_type: Option<String>,
+    /// Ticket template used at setup (`tix setup --template`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Issue details fetched from the tracker (`setup --from-tracker`, `tix issue refresh`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<IssueDetails>,
+    /// Lifecycle status (`todo`, `in-progress`, `review`, `blocked`, `done`).
+    #[serde(default)]
+    pub status: Option<TicketStatus>,
+    /// Lifecycle status changes, oldest first.
+    #[serde(default)]
+    pub status_history: Vec<StatusTransition>,
+    /// Files carried into each worktree (`carry_files`), keyed by repo alias.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub repo_carried_files: HashMap<String, CarriedFiles>,
+    /// Pull requests opened with `tix pr create`, keyed by repo alias.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pull_requests: HashMap<String, PullRequestRef>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// A pull request opened for one of the ticket's repo branches.
+pub struct PullRequestRef {
+    /// GitHub repository (`owner/name`).
+    pub repository: String,
+    /// Pull request number.
+    pub number: u64,
+    /// Web URL of the pull request.
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+/// Files carried from a repo's main checkout into its ticket worktree.
+pub struct CarriedFiles {
+    /// Patterns that selected the files (from `carry_files`).
+    pub patterns: Vec<String>,
+    /// Whether the files were copied or symlinked.
+    #[serde(default)]
+    pub mode: CarryMode,
+    /// Carried files, relative to the worktree root.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+/// Issue fields fetched from the issue tracker.
+pub struct IssueDetails {
+    /// Issue summary (title).
+    pub summary: String,
+    /// Issue type (e.g., `Bug`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<String>,
+    /// Assignee display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Tracker status (e.g., `In Progress`); independent of the tix lifecycle status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// When the fields were fetched (ISO 8601).
+    pub fetched_at: String,
+}
+
+impl TicketMetadata {
+    /// Files carried into the worktree for `alias` (empty when none were).
+    pub fn carried_files(&self, alias: &str) -> &[PathBuf] {
+        self.repo_carried_files
+            .get(alias)
+            .map(|c| c.files.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Remote settings for `alias` in this ticket: the repo's, with the base branch recorded
+    /// at setup taking precedence over the repo's `base_branch`.
+    pub fn remote_options<'a>(
+        &'a self,
+        alias: &str,
+        repo: &'a RepoDefinition,
+    ) -> RemoteOptions<'a> {
+        let mut options = repo.remote_options();
+        if let Some(base) = self.repo_base_branches.get(alias) {
+            options.base_branch = Some(base);
+        }
+        options
+    }
 }
 
 /// Represents a ticket workspace and its metadata.
@@ -40,13 +173,15 @@ pub struct Ticket {
 }
 
 impl Ticket {
-    /// Create a new `.tix/info.toml` stamp under `root` for the given ticket `id`.
+    /// Create a new `.tix/info.toml` stamp under `root` for the given ticket `id`, recording the
+    /// ticket `template` it was created from, if any.
     pub fn create(
         root: &Path,
         id: &str,
         description: Option<&String>,
         default_branch: &str,
         repo_branches: &[(String, String)],
+        template: Option<&str>,
     ) -> Result<Self> {
         let stamp_dir = root.join(STAMP_DIR);
         fs::create_dir_all(&stamp_dir).context("Failed to create .tix directory")?;
@@ -59,15 +194,29 @@ impl Ticket {
         }
 
         let repos = repo_branch_map.keys().cloned().collect();
+        let created_at = chrono::Local::now().to_rfc3339();
+        let status = TicketStatus::default();
 
         let metadata = TicketMetadata {
             id: id.to_string(),
             description: description.cloned(),
-            created_at: chrono::Local::now().to_rfc3339(),
+            created_at: created_at.clone(),
             branch: default_branch.to_string(),
             repos,
             repo_branches: repo_branch_map,
             repo_worktrees: repo_worktree_map,
+            repo_base_branches: HashMap::new(),
+            ticket_type: None,
+            template: template.map(str::to_string),
+            issue: None,
+            repo_carried_files: HashMap::new(),
+            pull_requests: HashMap::new(),
+            status: Some(status),
+            status_history: vec![StatusTransition {
+                from: None,
+                to: status,
+                at: created_at,
+            }],
         };
 
         // Write info.toml
@@ -102,6 +251,10 @@ impl Ticket {
                     .insert(alias.clone(), worktree_name_for_branch(&metadata.branch));
             }
         }
+        // Compatibility: tickets created before lifecycle tracking are in progress.
+        if metadata.status.is_none() {
+            metadata.status = Some(TicketStatus::default());
+        }
         // Compatibility: ensure repo_worktrees populated for existing branches.
         for (alias, branch) in metadata.repo_branches.clone() {
             metadata
@@ -116,27 +269,6 @@ impl Ticket {
         })
     }
 
-    /// Add repo aliases to the metadata with a given branch, preserving uniqueness and not overwriting existing branches.
-    pub fn add_repos_with_branch(root: &Path, repos: &[String], branch: &str) -> Result<()> {
-        let mut ticket = Ticket::load(root)?;
-        for r in repos {
-            if !ticket.metadata.repos.contains(r) {
-                ticket.metadata.repos.push(r.clone());
-            }
-            ticket
-                .metadata
-                .repo_branches
-                .entry(r.clone())
-                .or_insert_with(|| branch.to_string());
-            ticket
-                .metadata
-                .repo_worktrees
-                .entry(r.clone())
-                .or_insert_with(|| worktree_name_for_branch(branch));
-        }
-        write_metadata(root, &ticket.metadata)
-    }
-
     /// Add a single repo->branch mapping.
     pub fn add_repo_branch(root: &Path, repo: &str, branch: &str) -> Result<()> {
         let mut ticket = Ticket::load(root)?;
@@ -156,12 +288,86 @@ impl Ticket {
         write_metadata(root, &ticket.metadata)
     }
 
+    /// Record the base branch `repo`'s ticket branch was created from.
+    pub fn set_repo_base_branch(root: &Path, repo: &str, base: &str) -> Result<()> {
+        let mut ticket = Ticket::load(root)?;
+        ticket
+            .metadata
+            .repo_base_branches
+            .insert(repo.to_string(), base.to_string());
+        write_metadata(root, &ticket.metadata)
+    }
+
     /// Remove a repo alias from metadata.
     pub fn remove_repo(root: &Path, repo: &str) -> Result<()> {
         let mut ticket = Ticket::load(root)?;
         ticket.metadata.repos.retain(|existing| existing != repo);
         ticket.metadata.repo_branches.remove(repo);
         ticket.metadata.repo_worktrees.remove(repo);
+        ticket.metadata.repo_base_branches.remove(repo);
+        ticket.metadata.repo_carried_files.remove(repo);
+        ticket.metadata.pull_requests.remove(repo);
+        write_metadata(root, &ticket.metadata)
+    }
+
+    /// Record the pull request opened for `repo`.
+    pub fn set_pull_request(root: &Path, repo: &str, pull_request: PullRequestRef) -> Result<()> {
+        let mut ticket = Ticket::load(root)?;
+        ticket
+            .metadata
+            .pull_requests
+            .insert(repo.to_string(), pull_request);
+        write_metadata(root, &ticket.metadata)
+    }
+
+    /// Record the files carried into the worktree for `repo`.
+    pub fn set_carried_files(root: &Path, repo: &str, carried: CarriedFiles) -> Result<()> {
+        let mut ticket = Ticket::load(root)?;
+        ticket
+            .metadata
+            .repo_carried_files
+            .insert(repo.to_string(), carried);
+        write_metadata(root, &ticket.metadata)
+    }
+
+    /// Change the lifecycle status and append a timestamped transition.
+    /// Returns the previous status; recording the current status again is a no-op.
+    pub fn set_status(root: &Path, status: TicketStatus) -> Result<Option<TicketStatus>> {
+        let mut ticket = Ticket::load(root)?;
+        let previous = ticket.metadata.status;
+        if previous == Some(status) {
+            return Ok(previous);
+        }
+        ticket.metadata.status = Some(status);
+        ticket.metadata.status_history.push(StatusTransition {
+            from: previous,
+            to: status,
+            at: chrono::Local::now().to_rfc3339(),
+        });
+        write_metadata(root, &ticket.metadata)?;
+        Ok(previous)
+    }
+
+    /// Record the ticket type used to pick the branch template.
+    pub fn set_ticket_type(root: &Path, ticket_type: &str) -> Result<()> {
+        let mut ticket = Ticket::load(root)?;
+        ticket.metadata.ticket_type = Some(ticket_type.to_string());
+        write_metadata(root, &ticket.metadata)
+    }
+
+    /// Record issue details fetched from the tracker. The description follows the summary
+    /// unless it was set to something else by hand.
+    pub fn set_issue(root: &Path, issue: IssueDetails) -> Result<()> {
+        let mut ticket = Ticket::load(root)?;
+        let previous_summary = ticket.metadata.issue.as_ref().map(|i| &i.summary);
+        let follows_summary = match &ticket.metadata.description {
+            None => true,
+            Some(description) => Some(description) == previous_summary,
+        };
+        if follows_summary && !issue.summary.is_empty() {
+            ticket.metadata.description = Some(issue.summary.clone());
+        }
+        ticket.metadata.issue = Some(issue);
         write_metadata(root, &ticket.metadata)
     }
 
@@ -183,7 +389,156 @@ fn write_metadata(root: &Path, metadata: &TicketMetadata) -> Result<()> {
     fs::write(stamp_dir.join(METADATA_FILE), toml_string)?;
     Ok(())
 }
+
+/// Path to the `.tix` stamp directory for the ticket at `root`.
+pub fn stamp_dir(root: &Path) -> PathBuf {
+    root.join(STAMP_DIR)
+}
+
+/// Path to the metadata file (`.tix/info.toml`) for the ticket at `root`.
+pub fn metadata_path(root: &Path) -> PathBuf {
+    root.join(STAMP_DIR).join(METADATA_FILE)
+}
+
 /// Sanitize a branch name for use as a git worktree name.
 pub fn worktree_name_for_branch(branch: &str) -> String {
     branch.replace('/', "_")
 }
+
+/// Directory under `tickets_directory` holding archived ticket workspaces.
+pub fn archive_root(tickets_directory: &Path) -> PathBuf {
+    tickets_directory.join(ARCHIVE_DIR)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// A repo worktree removed by `tix archive`.
+pub struct ArchivedRepo {
+    /// Branch the worktree had checked out.
+    pub branch: String,
+    /// Commit the branch pointed at when archived, used to recreate a deleted branch.
+    #[serde(default)]
+    pub head: Option<String>,
+    /// Worktree name registered in the source repository.
+    pub worktree_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+/// Record written to `.tix/archive.toml` when a ticket is archived.
+pub struct ArchiveRecord {
+    /// Archive timestamp (ISO 8601).
+    pub archived_at: String,
+    /// Archived worktrees keyed by repo alias.
+    #[serde(default)]
+    pub repos: BTreeMap<String, ArchivedRepo>,
+}
+
+impl ArchiveRecord {
+    /// Load the archive record for the ticket at `root`.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(STAMP_DIR).join(ARCHIVE_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read archive record {:?}", path))?;
+        toml::from_str(&content).with_context(|| format!("Invalid archive record {:?}", path))
+    }
+
+    /// Whether the ticket at `root` has an archive record (archived, or partly restored).
+    pub fn exists(root: &Path) -> bool {
+        root.join(STAMP_DIR).join(ARCHIVE_FILE).exists()
+    }
+
+    /// Write this record under `root/.tix/`.
+    pub fn write(&self, root: &Path) -> Result<()> {
+        let stamp_dir = root.join(STAMP_DIR);
+        fs::create_dir_all(&stamp_dir).context("Failed to create .tix directory")?;
+        fs::write(stamp_dir.join(ARCHIVE_FILE), toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Delete the archive record for the ticket at `root`, if present.
+    pub fn remove(root: &Path) -> Result<()> {
+        let path = root.join(STAMP_DIR).join(ARCHIVE_FILE);
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn load_defaults_legacy_tickets_to_in_progress() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(stamp_dir(temp.path())).unwrap();
+        fs::write(
+            metadata_path(temp.path()),
+            "id = \"JIRA-1\"\ncreated_at = \"2024-01-01T00:00:00Z\"\n",
+        )
+        .unwrap();
+
+        let ticket = Ticket::load(temp.path()).unwrap();
+        assert_eq!(ticket.metadata.status, Some(TicketStatus::InProgress));
+        assert!(ticket.metadata.status_history.is_empty());
+    }
+
+    #[test]
+    fn set_status_records_transitions() {
+        let temp = TempDir::new().unwrap();
+        Ticket::create(temp.path(), "JIRA-1", None, "feature/JIRA-1", &[], None).unwrap();
+
+        let previous = Ticket::set_status(temp.path(), TicketStatus::Review).unwrap();
+        assert_eq!(previous, Some(TicketStatus::InProgress));
+        Ticket::set_status(temp.path(), TicketStatus::Review).unwrap();
+
+        let metadata = Ticket::load(temp.path()).unwrap().metadata;
+        assert_eq!(metadata.status, Some(TicketStatus::Review));
+        let history: Vec<_> = metadata
+            .status_history
+            .iter()
+            .map(|t| (t.from, t.to))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (None, TicketStatus::InProgress),
+                (Some(TicketStatus::InProgress), TicketStatus::Review)
+            ]
+        );
+        let raw = fs::read_to_string(metadata_path(temp.path())).unwrap();
+        assert!(raw.contains("status = \"review\""));
+    }
+
+    #[test]
+    fn set_issue_keeps_hand_written_descriptions() {
+        let temp = TempDir::new().unwrap();
+        Ticket::create(temp.path(), "JIRA-1", None, "feature/JIRA-1", &[], None).unwrap();
+        let issue = |summary: &str| IssueDetails {
+            summary: summary.into(),
+            fetched_at: "2024-01-01T00:00:00Z".into(),
+            ..Default::default()
+        };
+
+        Ticket::set_issue(temp.path(), issue("Fix login")).unwrap();
+        Ticket::set_issue(temp.path(), issue("Fix login on mobile")).unwrap();
+        let metadata = Ticket::load(temp.path()).unwrap().metadata;
+        assert_eq!(metadata.description.as_deref(), Some("Fix login on mobile"));
+
+        let custom = "Login tweaks".to_string();
+        Ticket::create(
+            temp.path(),
+            "JIRA-1",
+            Some(&custom),
+            "feature/JIRA-1",
+            &[],
+            None,
+        )
+        .unwrap();
+        Ticket::set_issue(temp.path(), issue("Fix login")).unwrap();
+        let metadata = Ticket::load(temp.path()).unwrap().metadata;
+        assert_eq!(metadata.description.as_deref(), Some("Login tweaks"));
+        assert_eq!(metadata.issue.unwrap().summary, "Fix login");
+    }
+}
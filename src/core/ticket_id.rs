@@ -0,0 +1,123 @@
+//! Ticket id validation and normalization.
+//!
+//! Ticket ids name directories under `tickets_directory`, so every id passed on the command line
+//! goes through [`normalize`] before it is joined onto a path: the configured `ticket_id_case`
+//! is applied, path-traversal and whitespace are rejected, and `ticket_id_pattern` is enforced.
+
+use crate::core::config::{Config, TicketIdCase};
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Compile `ticket_id_pattern`; the pattern must match the whole id.
+pub fn compile_pattern(pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("^(?:{})$", pattern))
+        .with_context(|| format!("Invalid ticket_id_pattern '{}'", pattern))
+}
+
+/// Apply the configured case policy to `raw` and validate the result.
+/// Returns the canonical ticket id.
+pub fn normalize(config: &Config, raw: &str) -> Result<String> {
+    let id = match config.ticket_id_case {
+        TicketIdCase::Preserve => raw.to_string(),
+        TicketIdCase::Upper => raw.to_uppercase(),
+        TicketIdCase::Lower => raw.to_lowercase(),
+    };
+    ensure_safe(&id)?;
+    if let Some(pattern) = &config.ticket_id_pattern
+        && !compile_pattern(pattern)?.is_match(&id)
+    {
+        bail!(
+            "Ticket id '{}' does not match ticket_id_pattern '{}'",
+            id,
+            pattern
+        );
+    }
+    Ok(id)
+}
+
+/// Reject ids that would escape `tickets_directory`, collide with tix's own entries
+/// (e.g. `.archive`) or be awkward to use in a shell.
+pub fn ensure_safe(id: &str) -> Result<()> {
+    if id.is_empty() {
+        bail!("Ticket id cannot be empty");
+    }
+    if id.contains(['/', '\\']) {
+        bail!("Invalid ticket id '{}': must not contain '/' or '\\'", id);
+    }
+    if id.starts_with('.') {
+        bail!("Invalid ticket id '{}': must not start with '.'", id);
+    }
+    if id.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        bail!(
+            "Invalid ticket id '{}': must not contain whitespace or control characters",
+            id.escape_debug()
+        );
+    }
+    if id.contains(':') {
+        bail!("Invalid ticket id '{}': must not contain ':'", id);
+    }
+    Ok(())
+}
+
+/// Workspace directory for the ticket id given on the command line.
+pub fn ticket_dir(config: &Config, raw: &str) -> Result<PathBuf> {
+    Ok(config.tickets_directory.join(normalize(config, raw)?))
+}
+
+/// Name of an entry in `dir` that differs from `id` only by case, if any.
+pub fn find_case_variant(dir: &Path, id: &str) -> Option<String> {
+    let wanted = id.to_lowercase();
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .find(|name| name != id && name.to_lowercase() == wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rejects_path_traversal_and_whitespace() {
+        for id in [
+            "", "..", "../etc", "a/b", "a\\b", ".archive", "JIRA 1", "C:x",
+        ] {
+            assert!(ensure_safe(id).is_err(), "{:?} should be rejected", id);
+        }
+        ensure_safe("JIRA-123").unwrap();
+        ensure_safe("fix_login.v2").unwrap();
+    }
+
+    #[test]
+    fn normalize_applies_case_then_pattern() {
+        let mut config = Config {
+            ticket_id_case: TicketIdCase::Upper,
+            ticket_id_pattern: Some("[A-Z]+-[0-9]+".into()),
+            ..Default::default()
+        };
+        assert_eq!(normalize(&config, "jira-123").unwrap(), "JIRA-123");
+        let err = normalize(&config, "jira-12a").unwrap_err();
+        assert!(err.to_string().contains("does not match ticket_id_pattern"));
+
+        // The pattern is anchored: a matching substring is not enough.
+        config.ticket_id_case = TicketIdCase::Preserve;
+        assert!(normalize(&config, "xJIRA-1").is_err());
+        assert!(normalize(&config, "../JIRA-1").is_err());
+    }
+
+    #[test]
+    fn finds_case_variants() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("JIRA-123")).unwrap();
+        assert_eq!(
+            find_case_variant(dir.path(), "jira-123").as_deref(),
+            Some("JIRA-123")
+        );
+        assert_eq!(find_case_variant(dir.path(), "JIRA-123"), None);
+        assert_eq!(find_case_variant(dir.path(), "JIRA-124"), None);
+    }
+}
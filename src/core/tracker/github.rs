@@ -0,0 +1,192 @@
+//! GitHub Issues tracker backend.
+//!
+//! Ticket ids are issue numbers, optionally with a prefix (`123`, `GH-123`).
+
+use crate::core::config::{Config, TrackerKind};
+use crate::core::github::{self, GitHubClient};
+use crate::core::ticket::IssueDetails;
+use crate::core::tracker::IssueTracker;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    title: String,
+    state: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    assignee: Option<User>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+}
+
+/// Issues of a single GitHub repository.
+pub struct GitHubIssues {
+    /// `owner/name` of the repository holding the issues.
+    repository: String,
+    /// Base for issue links (e.g., `https://github.com/my-org/issues/issues`).
+    web_url: String,
+    /// Site URL used to look up credentials.
+    site_url: String,
+    api_url: String,
+    issue_type_prefixes: HashMap<String, String>,
+}
+
+impl GitHubIssues {
+    /// GitHub Issues settings from `[tracker]` and `github_base_url`.
+    pub fn from_config(config: &Config) -> Self {
+        let tracker = config
+            .tracker
+            .as_ref()
+            .filter(|t| t.kind == TrackerKind::GitHub);
+        let repository = tracker
+            .and_then(|t| t.repository.clone())
+            .unwrap_or_default();
+        let site_url = config.github_base_url.trim_end_matches('/').to_string();
+        let web_url = tracker
+            .and_then(|t| t.url.as_ref())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("{}/{}/issues", site_url, repository));
+        let api_url = tracker
+            .and_then(|t| t.api_url.clone())
+            .unwrap_or_else(|| github::api_url(config));
+        GitHubIssues {
+            repository,
+            web_url,
+            site_url,
+            api_url,
+            issue_type_prefixes: tracker
+                .map(|t| t.issue_type_prefixes.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Issue number in `ticket_id` (`123` or `<prefix>-123`).
+    fn issue_number<'a>(&self, ticket_id: &'a str) -> Option<&'a str> {
+        let (prefix, number) = match ticket_id.rsplit_once('-') {
+            Some((prefix, number)) => (prefix, number),
+            None => ("", ticket_id),
+        };
+        let prefix_ok = prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        let number_ok = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+        (prefix_ok && number_ok).then_some(number)
+    }
+}
+
+impl IssueTracker for GitHubIssues {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn validate_id(&self, ticket_id: &str) -> Result<()> {
+        if self.issue_number(ticket_id).is_none() {
+            bail!(
+                "'{}' is not a GitHub issue id (expected 123 or GH-123)",
+                ticket_id
+            );
+        }
+        Ok(())
+    }
+
+    fn issue_url(&self, ticket_id: &str) -> Option<String> {
+        let number = self.issue_number(ticket_id)?;
+        Some(format!("{}/{}", self.web_url, number))
+    }
+
+    fn fetch_issue(&self, ticket_id: &str) -> Result<IssueDetails> {
+        self.validate_id(ticket_id)?;
+        let number = self.issue_number(ticket_id).unwrap_or_default();
+        let client = GitHubClient::new(&self.api_url, &self.site_url)?;
+        let path = format!("/repos/{}/issues/{}", self.repository, number);
+        let Some(issue) = client.get_optional::<IssueResponse>(&path)? else {
+            bail!(
+                "GitHub issue #{} was not found in {}",
+                number,
+                self.repository
+            );
+        };
+        Ok(self.details(issue))
+    }
+
+    fn issue_type_prefixes(&self) -> &HashMap<String, String> {
+        &self.issue_type_prefixes
+    }
+}
+
+impl GitHubIssues {
+    /// Map the API response; the issue type is the first label with a branch prefix mapping,
+    /// else the first label.
+    fn details(&self, issue: IssueResponse) -> IssueDetails {
+        let issue_type = issue
+            .labels
+            .iter()
+            .find(|l| self.branch_prefix_for(&l.name).is_some())
+            .or(issue.labels.first())
+            .map(|l| l.name.clone());
+        IssueDetails {
+            summary: issue.title,
+            issue_type,
+            assignee: issue.assignee.map(|a| a.login),
+            status: Some(issue.state),
+            fetched_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::TrackerConfig;
+
+    fn tracker() -> GitHubIssues {
+        GitHubIssues::from_config(&Config {
+            github_base_url: "https://github.com".into(),
+            tracker: Some(TrackerConfig {
+                kind: TrackerKind::GitHub,
+                repository: Some("my-org/issues".into()),
+                issue_type_prefixes: [("bug".to_string(), "bugfix".to_string())].into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn issue_ids_and_links() {
+        let tracker = tracker();
+        assert_eq!(
+            tracker.issue_url("GH-42").as_deref(),
+            Some("https://github.com/my-org/issues/issues/42")
+        );
+        assert_eq!(tracker.issue_number("42"), Some("42"));
+        assert!(tracker.validate_id("JIRA-x").is_err());
+        assert!(tracker.validate_id("a/b-1").is_err());
+        assert_eq!(tracker.issue_url("feature"), None);
+    }
+
+    #[test]
+    fn details_prefer_labels_with_prefixes() {
+        let issue: IssueResponse = serde_json::from_str(
+            r#"{"title": "Crash on start", "state": "open",
+                "labels": [{"name": "p1"}, {"name": "Bug"}], "assignee": {"login": "octocat"}}"#,
+        )
+        .unwrap();
+        let details = tracker().details(issue);
+        assert_eq!(details.summary, "Crash on start");
+        assert_eq!(details.issue_type.as_deref(), Some("Bug"));
+        assert_eq!(details.assignee.as_deref(), Some("octocat"));
+        assert_eq!(details.status.as_deref(), Some("open"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Jira issue tracker backend (REST API v2).
+//!
+//! Credentials come from `TIX_JIRA_TOKEN` (with `TIX_JIRA_USER` for basic auth, as used by
+//! Jira Cloud API tokens; alone it is sent as a bearer token), falling back to
+//! `git credential fill` for the Jira host.
+
+use crate::core::config::{Config, TrackerKind};
+use crate::core::credentials::{self, Credential};
+use crate::core::http;
+use crate::core::ticket::IssueDetails;
+use crate::core::tracker::IssueTracker;
+use anyhow::{Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use log::debug;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+
+/// API token (or password) for Jira.
+pub const TOKEN_ENV: &str = "TIX_JIRA_TOKEN";
+/// Username (usually an email address) paired with `TIX_JIRA_TOKEN` for basic auth.
+pub const USER_ENV: &str = "TIX_JIRA_USER";
+
+/// Fields requested from the issue endpoint.
+const ISSUE_FIELDS: &str = "summary,issuetype,assignee,status";
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    fields: IssueFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueFields {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    issuetype: Option<Named>,
+    #[serde(default)]
+    assignee: Option<Person>,
+    #[serde(default)]
+    status: Option<Named>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+/// A Jira site.
+pub struct Jira {
+    /// Base for issue links (e.g., `https://company.atlassian.net/browse`).
+    browse_url: Option<String>,
+    /// REST base URL (e.g., `https://company.atlassian.net`).
+    api_url: Option<String>,
+    issue_type_prefixes: HashMap<String, String>,
+}
+
+impl Jira {
+    /// Jira settings from `[tracker]` (when `kind = "jira"`) and `jira_base_url`.
+    pub fn from_config(config: &Config) -> Self {
+        let tracker = config
+            .tracker
+            .as_ref()
+            .filter(|t| t.kind == TrackerKind::Jira);
+        let browse_url = tracker
+            .and_then(|t| t.url.as_ref())
+            .or(config.jira_base_url.as_ref())
+            .map(|url| url.trim_end_matches('/').to_string());
+        let api_url = tracker
+            .and_then(|t| t.api_url.as_ref())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .or_else(|| {
+                browse_url
+                    .as_deref()
+                    .map(|url| url.strip_suffix("/browse").unwrap_or(url).to_string())
+            });
+        let issue_type_prefixes = tracker
+            .map(|t| t.issue_type_prefixes.clone())
+            .unwrap_or_default();
+        Jira {
+            browse_url,
+            api_url,
+            issue_type_prefixes,
+        }
+    }
+}
+
+impl IssueTracker for Jira {
+    fn name(&self) -> &'static str {
+        "Jira"
+    }
+
+    fn validate_id(&self, ticket_id: &str) -> Result<()> {
+        let valid = ticket_id.split_once('-').is_some_and(|(project, number)| {
+            project.starts_with(|c: char| c.is_ascii_alphabetic())
+                && project
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+        });
+        if !valid {
+            bail!(
+                "'{}' is not a Jira issue key (expected PROJECT-123)",
+                ticket_id
+            );
+        }
+        Ok(())
+    }
+
+    fn issue_url(&self, ticket_id: &str) -> Option<String> {
+        let base = self.browse_url.as_ref()?;
+        Some(format!("{}/{}", base, ticket_id))
+    }
+
+    fn fetch_issue(&self, ticket_id: &str) -> Result<IssueDetails> {
+        self.validate_id(ticket_id)?;
+        let api_url = self.api_url.as_ref().ok_or_else(|| {
+            anyhow!("Jira is not configured; set jira_base_url (and optionally tracker.api_url)")
+        })?;
+        let url = format!("{}/rest/api/2/issue/{}", api_url, ticket_id);
+        debug!("GET {}", url);
+        let mut request = http::agent()
+            .get(&url)
+            .query("fields", ISSUE_FIELDS)
+            .header("Accept", "application/json")
+            .header("User-Agent", http::USER_AGENT);
+        match authorization(api_url)? {
+            Some(authorization) => request = request.header("Authorization", &authorization),
+            None => debug!("No Jira credentials found; sending an anonymous request"),
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(404)) => {
+                bail!("Jira issue '{}' was not found", ticket_id)
+            }
+            Err(ureq::Error::StatusCode(code @ (401 | 403))) => bail!(
+                "Jira rejected the request for '{}' (HTTP {}); check {} / {} or your git credentials",
+                ticket_id,
+                code,
+                USER_ENV,
+                TOKEN_ENV
+            ),
+            Err(e) => bail!("Failed to fetch Jira issue '{}': {}", ticket_id, e),
+        };
+        let issue: IssueResponse = response
+            .into_body()
+            .read_json()
+            .map_err(|e| anyhow!("Failed to parse Jira issue '{}': {}", ticket_id, e))?;
+        Ok(issue.into_details())
+    }
+
+    fn issue_type_prefixes(&self) -> &HashMap<String, String> {
+        &self.issue_type_prefixes
+    }
+}
+
+impl IssueResponse {
+    fn into_details(self) -> IssueDetails {
+        IssueDetails {
+            summary: self.fields.summary,
+            issue_type: self.fields.issuetype.map(|t| t.name),
+            assignee: self.fields.assignee.map(|a| a.display_name),
+            status: self.fields.status.map(|s| s.name),
+            fetched_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+}
+
+fn authorization(api_url: &str) -> Result<Option<String>> {
+    let token = env::var(TOKEN_ENV).ok().filter(|t| !t.is_empty());
+    let user = env::var(USER_ENV).ok().filter(|u| !u.is_empty());
+    let credential = match (user, token) {
+        (Some(username), Some(password)) => Credential { username, password },
+        (None, Some(token)) => return Ok(Some(format!("Bearer {}", token))),
+        (_, None) => match credentials::git_credential_fill(api_url)? {
+            Some(credential) => credential,
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(basic_auth(&credential)))
+}
+
+fn basic_auth(credential: &Credential) -> String {
+    let raw = format!("{}:{}", credential.username, credential.password);
+    format!("Basic {}", STANDARD.encode(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::TrackerConfig;
+
+    #[test]
+    fn api_url_strips_browse_suffix() {
+        let mut config = Config {
+            jira_base_url: Some("https://company.atlassian.net/browse/".into()),
+            ..Default::default()
+        };
+        let jira = Jira::from_config(&config);
+        assert_eq!(
+            jira.api_url.as_deref(),
+            Some("https://company.atlassian.net")
+        );
+        assert_eq!(
+            jira.issue_url("JIRA-123").as_deref(),
+            Some("https://company.atlassian.net/browse/JIRA-123")
+        );
+        // Links do not require an issue key; only fetching does.
+        assert_eq!(
+            jira.issue_url("hotfix-login").as_deref(),
+            Some("https://company.atlassian.net/browse/hotfix-login")
+        );
+        config.tracker = Some(TrackerConfig {
+            api_url: Some("https://jira.internal/".into()),
+            ..Default::default()
+        });
+        let jira = Jira::from_config(&config);
+        assert_eq!(jira.api_url.as_deref(), Some("https://jira.internal"));
+    }
+
+    #[test]
+    fn validate_id_accepts_issue_keys_only() {
+        let jira = Jira::from_config(&Config::default());
+        assert!(jira.validate_id("JIRA-123").is_ok());
+        assert!(jira.validate_id("web_2-7").is_ok());
+        assert!(jira.validate_id("123").is_err());
+        assert!(jira.validate_id("JIRA-").is_err());
+        assert!(jira.validate_id("2FA-1").is_err());
+        assert!(jira.validate_id("JIRA-1/../x").is_err());
+    }
+
+    #[test]
+    fn issue_response_maps_optional_fields() {
+        let issue: IssueResponse = serde_json::from_str(
+            r#"{"key": "JIRA-1", "fields": {"summary": "Fix login", "issuetype": {"name": "Bug"},
+                "assignee": null, "status": {"name": "In Progress"}}}"#,
+        )
+        .unwrap();
+        let details = issue.into_details();
+        assert_eq!(details.summary, "Fix login");
+        assert_eq!(details.issue_type.as_deref(), Some("Bug"));
+        assert_eq!(details.assignee, None);
+        assert_eq!(details.status.as_deref(), Some("In Progress"));
+    }
+
+    #[test]
+    fn branch_prefix_for_ignores_case() {
+        let config = Config {
+            jira_base_url: Some("https://company.atlassian.net/browse".into()),
+            tracker: Some(TrackerConfig {
+                issue_type_prefixes: [("Bug".to_string(), "bugfix".to_string())].into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let jira = Jira::from_config(&config);
+        assert_eq!(jira.branch_prefix_for("bug"), Some("bugfix"));
+        assert_eq!(jira.branch_prefix_for("Epic"), None);
+    }
+
+    #[test]
+    fn basic_auth_encodes_credentials() {
+        let credential = Credential {
+            username: "me@example.com".into(),
+            password: "token".into(),
+        };
+        assert_eq!(
+            basic_auth(&credential),
+            "Basic bWVAZXhhbXBsZS5jb206dG9rZW4="
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Issue tracker backends (`[tracker]` in config): issue links, ticket id validation and
+//! fetching issue details.
+
+pub mod github;
+pub mod jira;
+
+use crate::core::config::{Config, TrackerKind};
+use crate::core::ticket::IssueDetails;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// An issue tracker that ticket ids refer to.
+pub trait IssueTracker {
+    /// Display name (e.g., `Jira`).
+    fn name(&self) -> &'static str;
+
+    /// Check that `ticket_id` names an issue in this tracker before fetching it.
+    fn validate_id(&self, ticket_id: &str) -> Result<()>;
+
+    /// Browser link for the issue, or `None` when links are not configured or the id cannot be
+    /// turned into one (ids need not pass `validate_id` to be linked).
+    fn issue_url(&self, ticket_id: &str) -> Option<String>;
+
+    /// Fetch the issue's title, status, type and assignee.
+    fn fetch_issue(&self, ticket_id: &str) -> Result<IssueDetails>;
+
+    /// Branch prefixes keyed by issue type.
+    fn issue_type_prefixes(&self) -> &HashMap<String, String>;
+
+    /// Branch prefix mapped from an issue type (case-insensitive).
+    fn branch_prefix_for(&self, issue_type: &str) -> Option<&str> {
+        self.issue_type_prefixes()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(issue_type))
+            .map(|(_, prefix)| prefix.as_str())
+    }
+}
+
+/// Tracker configured by `[tracker]`, or Jira when only `jira_base_url` is set.
+pub fn from_config(config: &Config) -> Option<Box<dyn IssueTracker>> {
+    match &config.tracker {
+        Some(tracker) => match tracker.kind {
+            TrackerKind::Jira => Some(Box::new(jira::Jira::from_config(config))),
+            TrackerKind::GitHub => Some(Box::new(github::GitHubIssues::from_config(config))),
+        },
+        None if config.jira_base_url.is_some() => Some(Box::new(jira::Jira::from_config(config))),
+        None => None,
+    }
+}
+
+/// Like `from_config`, but errors when no tracker is configured.
+pub fn require(config: &Config) -> Result<Box<dyn IssueTracker>> {
+    from_config(config).ok_or_else(|| {
+        anyhow::anyhow!("No issue tracker configured; add a [tracker] section or set jira_base_url")
+    })
+}
+
+/// Link to the issue for `ticket_id`, if a tracker is configured and can link the id.
+pub fn issue_link(config: &Config, ticket_id: &str) -> Option<String> {
+    from_config(config)?.issue_url(ticket_id)
+}
@@ -2,4 +2,4 @@
 
 pub mod core;
 // Re-export core modules for convenient `tix::git`, etc.
-pub use core::{cli, commands, config, git, plugins, ticket};
+pub use core::{cli, commands, config, git, output, plugins, ticket};
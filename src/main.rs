@@ -2,7 +2,10 @@ mod core;
 
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser};
-use core::cli::{Cli, Commands, PluginCommands};
+use core::cli::{
+    Cli, Commands, GroupCommands, IssueCommands, PluginCommands, PrCommands, StatusCommands,
+};
+use core::output::OutputFormat;
 use log::{debug, error};
 use std::backtrace::{Backtrace, BacktraceStatus};
 use std::process;
@@ -15,6 +18,23 @@ fn main() -> Result<()> {
     let log_level = args.verbose.log_level_filter();
     env_logger::Builder::new().filter_level(log_level).init();
 
+    if args.format != OutputFormat::Table && !args.command.supports_format() {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--format json|tsv is only supported by list, info, status and pr status",
+            )
+            .exit();
+    }
+    if args.dry_run && !args.command.supports_dry_run() {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--dry-run is only supported by setup, add, remove, destroy, setup-repos, add-repo and gc",
+            )
+            .exit();
+    }
+
     // 3. Dispatch commands
     let result = match args.command {
         Commands::Completions { shell } => {
@@ -49,28 +69,111 @@ fn main() -> Result<()> {
             Ok(())
         }
         Commands::Add {
-            repo,
+            repos,
             ticket,
             branch,
-        } => core::commands::add::run(&repo, ticket.as_deref(), branch.as_deref()),
-        Commands::AddRepo { repo, alias } => core::commands::add_repo::run(&repo, alias),
+        } => core::commands::add::run(&repos, ticket.as_deref(), branch.as_deref(), args.dry_run),
+        Commands::Archive { ticket, force } => core::commands::archive::run(&ticket, force),
+        Commands::AddRepo { repo, alias } => {
+            core::commands::add_repo::run(&repo, alias, args.dry_run)
+        }
         Commands::Config { key, value, edit } => {
             core::commands::config_cmd::run(key.as_deref(), value.as_deref(), edit)
         }
-        Commands::Destroy { ticket, force } => core::commands::destroy::run(&ticket, force),
+        Commands::Destroy {
+            ticket,
+            force,
+            allow_unpushed,
+            delete_branches,
+            delete_remote_branches,
+        } => core::commands::destroy::run(
+            &ticket,
+            force,
+            allow_unpushed,
+            delete_branches,
+            delete_remote_branches,
+            args.dry_run,
+        ),
+        Commands::Exec {
+            ticket,
+            repos,
+            parallel,
+            command,
+        } => core::commands::exec::run(ticket.as_deref(), &repos, parallel, &command),
+        Commands::Gc { older_than, yes } => core::commands::gc::run(args.dry_run, older_than, yes),
         Commands::Init => core::commands::init::run(),
-        Commands::Remove { repo, ticket } => core::commands::remove::run(&repo, ticket.as_deref()),
+        Commands::Remove {
+            repos,
+            ticket,
+            allow_unpushed,
+        } => core::commands::remove::run(&repos, ticket.as_deref(), allow_unpushed, args.dry_run),
+        Commands::RefreshFiles { ticket } => core::commands::refresh_files::run(ticket.as_deref()),
+        Commands::Repair { ticket } => core::commands::repair::run(ticket.as_deref()),
+        Commands::Restore { ticket } => core::commands::restore::run(&ticket),
         Commands::Setup {
             ticket,
             all,
             repos,
             description,
-        } => core::commands::setup::run(&ticket, &repos, all, description),
-        Commands::SetupRepos => core::commands::setup_repos::run(),
-        Commands::Doctor => core::commands::doctor::run(),
+            keep_partial,
+            jobs,
+            ticket_type,
+            template,
+            from_tracker,
+        } => core::commands::setup::run(
+            &ticket,
+            core::commands::setup::SetupOptions {
+                repos: &repos,
+                all,
+                description,
+                keep_partial,
+                jobs,
+                ticket_type: ticket_type.as_deref(),
+                template: template.as_deref(),
+                from_tracker,
+                dry_run: args.dry_run,
+            },
+        ),
+        Commands::SetupRepos { jobs } => core::commands::setup_repos::run(jobs, args.dry_run),
+        Commands::Doctor { fix } => core::commands::doctor::run(fix),
         Commands::Update => core::commands::update::run(),
-        Commands::Info { ticket } => core::commands::info::run(ticket.as_deref()),
-        Commands::List => core::commands::list::run(),
+        Commands::Info { ticket } => core::commands::info::run(ticket.as_deref(), args.format),
+        Commands::List { archived, status } => {
+            core::commands::list::run(archived, status, args.format)
+        }
+        Commands::Status { ticket, command } => match command {
+            Some(StatusCommands::Set {
+                status,
+                ticket: set_ticket,
+            }) => core::commands::status::set(set_ticket.or(ticket).as_deref(), status),
+            None => core::commands::status::run(ticket.as_deref(), args.format),
+        },
+        Commands::Push {
+            ticket,
+            force_with_lease,
+        } => core::commands::push::run(ticket.as_deref(), force_with_lease),
+        Commands::Sync {
+            ticket,
+            rebase,
+            merge,
+            abort,
+        } => core::commands::sync::run(ticket.as_deref(), rebase, merge, abort),
+        Commands::Issue { command } => match command {
+            IssueCommands::Refresh { ticket } => core::commands::issue::refresh(ticket.as_deref()),
+        },
+        Commands::Pr { command } => match command {
+            PrCommands::Create { ticket, draft } => {
+                core::commands::pr::create(ticket.as_deref(), draft)
+            }
+            PrCommands::Status { ticket } => {
+                core::commands::pr::status(ticket.as_deref(), args.format)
+            }
+        },
+        Commands::Groups { command } => match command {
+            GroupCommands::List => core::commands::groups::list(),
+            GroupCommands::Add { name, repos } => core::commands::groups::add(&name, &repos),
+            GroupCommands::Remove { name, repos } => core::commands::groups::remove(&name, &repos),
+        },
         Commands::Plugins { command } => match command {
             PluginCommands::List => core::commands::plugins::list(),
             PluginCommands::Register {
@@ -3,7 +3,11 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use git2::{BranchType, Commit, Repository, Signature};
-use tix::git::{clone_repo, create_worktree, fetch_and_fast_forward, is_clean, remove_worktree};
+use tix::git::{
+    BranchRetirement, Divergence, RemoteOptions, branch_retirement, clone_repo, create_worktree,
+    fetch_and_fast_forward, fetch_pruned, is_clean, is_linked_worktree, is_merged,
+    registered_worktrees, relink_worktree, remove_worktree, unpublished_commits, worktree_status,
+};
 
 static COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -44,15 +48,14 @@ fn add_commit(repo: &Repository, filename: &str, contents: &str) -> Result<git2:
     let sig = Signature::now("Test", "test@example.com")?;
 
     let mut parents = Vec::new();
-    if let Ok(head) = repo.head() {
-        if let Some(oid) = head.target() {
-            parents.push(repo.find_commit(oid)?);
-        }
+    if let Ok(head) = repo.head()
+        && let Some(oid) = head.target()
+    {
+        parents.push(repo.find_commit(oid)?);
     }
     let parent_refs: Vec<&Commit> = parents.iter().collect();
 
     repo.commit(Some("HEAD"), &sig, &sig, "commit", &tree, &parent_refs)
-        .map_err(Into::into)
 }
 
 fn head_oid(repo_path: &Path) -> git2::Oid {
@@ -109,8 +112,15 @@ fn create_and_remove_worktree() {
     let branch_name = "feature/test";
     let worktree_name = branch_name.replace('/', "_");
 
-    let Some(_) = skip_if_xdev(|| create_worktree(&repo_path, &worktree_path, branch_name, None))
-    else {
+    let Some(_) = skip_if_xdev(|| {
+        create_worktree(
+            &repo_path,
+            &worktree_path,
+            branch_name,
+            None,
+            &RemoteOptions::default(),
+        )
+    }) else {
         return;
     };
     assert!(worktree_path.exists());
@@ -119,6 +129,43 @@ fn create_and_remove_worktree() {
     remove_worktree(&repo_path, &worktree_name).unwrap();
 }
 
+#[test]
+fn relink_worktree_repairs_a_moved_worktree() {
+    let repo_path = empty_dir("relink-src");
+    let Some(_) = skip_if_xdev(|| init_repo_with_commit(&repo_path)) else {
+        return;
+    };
+    let worktree_root = empty_dir("relink-root");
+    let original = worktree_root.join("before");
+    let Some(created) = skip_if_xdev(|| {
+        create_worktree(
+            &repo_path,
+            &original,
+            "feature/moved",
+            None,
+            &RemoteOptions::default(),
+        )
+    }) else {
+        return;
+    };
+    assert!(is_linked_worktree(&repo_path, &original));
+
+    let moved = worktree_root.join("after");
+    fs::rename(&original, &moved).unwrap();
+    let registered = registered_worktrees(&repo_path).unwrap();
+    assert_eq!(registered.len(), 1);
+    assert_eq!(registered[0].name, created.worktree_name);
+    assert!(!registered[0].path.exists());
+
+    relink_worktree(&repo_path, &created.worktree_name, &moved).unwrap();
+    assert!(is_linked_worktree(&repo_path, &moved));
+    let registered = registered_worktrees(&repo_path).unwrap();
+    assert_eq!(
+        registered[0].path.canonicalize().unwrap(),
+        moved.canonicalize().unwrap()
+    );
+}
+
 #[test]
 fn clone_repo_from_local_path() {
     let origin_path = empty_dir("origin");
@@ -127,7 +174,8 @@ fn clone_repo_from_local_path() {
     };
 
     let clone_path = empty_dir("clone");
-    let Some(_) = skip_if_xdev(|| clone_repo(origin_path.to_str().unwrap(), &clone_path)) else {
+    let Some(_) = skip_if_xdev(|| clone_repo(origin_path.to_str().unwrap(), &clone_path, "origin"))
+    else {
         return;
     };
     assert!(clone_path.join(".git").exists());
@@ -141,7 +189,8 @@ fn fetch_and_fast_forward_updates_clone() {
     };
 
     let clone_path = empty_dir("clone-ff");
-    let Some(_) = skip_if_xdev(|| clone_repo(origin_path.to_str().unwrap(), &clone_path)) else {
+    let Some(_) = skip_if_xdev(|| clone_repo(origin_path.to_str().unwrap(), &clone_path, "origin"))
+    else {
         return;
     };
 
@@ -151,7 +200,8 @@ fn fetch_and_fast_forward_updates_clone() {
         return;
     };
 
-    let Some(_) = skip_if_xdev(|| fetch_and_fast_forward(&clone_path, "origin")) else {
+    let Some(_) = skip_if_xdev(|| fetch_and_fast_forward(&clone_path, &RemoteOptions::default()))
+    else {
         return;
     };
 
@@ -181,8 +231,15 @@ fn create_worktree_sets_upstream_when_remote_branch_exists() {
     let worktree_path = worktree_root.join("dst");
     let branch_name = "feature/upstream";
 
-    let Some(_) = skip_if_xdev(|| create_worktree(&repo_path, &worktree_path, branch_name, None))
-    else {
+    let Some(_) = skip_if_xdev(|| {
+        create_worktree(
+            &repo_path,
+            &worktree_path,
+            branch_name,
+            None,
+            &RemoteOptions::default(),
+        )
+    }) else {
         return;
     };
 
@@ -193,3 +250,258 @@ fn create_worktree_sets_upstream_when_remote_branch_exists() {
     let upstream = local.upstream().unwrap();
     assert_eq!(upstream.name().unwrap().unwrap(), "origin/feature/upstream");
 }
+
+#[test]
+fn worktree_status_counts_changes_and_divergence() {
+    let repo_path = empty_dir("status-src");
+    let Some(_) = skip_if_xdev(|| init_repo_with_commit(&repo_path)) else {
+        return;
+    };
+
+    let worktree_root = empty_dir("status-root");
+    let worktree_path = worktree_root.join("dst");
+    let Some(_) = skip_if_xdev(|| {
+        create_worktree(
+            &repo_path,
+            &worktree_path,
+            "feature/status",
+            None,
+            &RemoteOptions::default(),
+        )
+    }) else {
+        return;
+    };
+
+    let worktree_repo = Repository::open(&worktree_path).unwrap();
+    let Some(_) = skip_if_xdev(|| add_commit(&worktree_repo, "ahead.txt", "ahead")) else {
+        return;
+    };
+
+    fs::write(worktree_path.join("README.md"), "modified").unwrap();
+    fs::write(worktree_path.join("staged.txt"), "staged").unwrap();
+    let mut index = worktree_repo.index().unwrap();
+    index.add_path(Path::new("staged.txt")).unwrap();
+    index.write().unwrap();
+    fs::write(worktree_path.join("untracked.txt"), "new").unwrap();
+
+    let base = tix::git::default_branch_for(&repo_path, &RemoteOptions::default());
+    let status = worktree_status(&worktree_path, base.as_deref()).unwrap();
+
+    assert_eq!(status.branch.as_deref(), Some("feature/status"));
+    assert!(status.upstream.is_none());
+    assert_eq!(
+        status.base_divergence,
+        Some(Divergence {
+            ahead: 1,
+            behind: 0
+        })
+    );
+    assert_eq!(status.staged, 1);
+    assert_eq!(status.unstaged, 1);
+    assert_eq!(status.untracked, 1);
+}
+
+#[test]
+fn unpublished_commits_reports_commits_missing_from_base_and_upstream() {
+    let repo_path = empty_dir("unpublished-src");
+    let Some(_) = skip_if_xdev(|| init_repo_with_commit(&repo_path)) else {
+        return;
+    };
+
+    let worktree_root = empty_dir("unpublished-root");
+    let worktree_path = worktree_root.join("dst");
+    let branch_name = "feature/unpublished";
+    let Some(_) = skip_if_xdev(|| {
+        create_worktree(
+            &repo_path,
+            &worktree_path,
+            branch_name,
+            None,
+            &RemoteOptions::default(),
+        )
+    }) else {
+        return;
+    };
+
+    let base = tix::git::default_branch_for(&repo_path, &RemoteOptions::default());
+    let before = unpublished_commits(&repo_path, branch_name, base.as_deref()).unwrap();
+    assert!(before.commits.is_empty());
+
+    let worktree_repo = Repository::open(&worktree_path).unwrap();
+    let Some(_) = skip_if_xdev(|| add_commit(&worktree_repo, "local.txt", "local")) else {
+        return;
+    };
+
+    let after = unpublished_commits(&repo_path, branch_name, base.as_deref()).unwrap();
+    assert_eq!(after.commits.len(), 1);
+    assert_eq!(after.commits[0].summary, "commit");
+    assert!(after.upstream.is_none());
+
+    // Publishing the commit to a remote-tracking upstream clears the finding.
+    let repo = Repository::open(&repo_path).unwrap();
+    repo.remote("origin", repo_path.to_str().unwrap()).unwrap();
+    let tip = repo
+        .find_branch(branch_name, BranchType::Local)
+        .unwrap()
+        .get()
+        .target()
+        .unwrap();
+    repo.reference(
+        "refs/remotes/origin/feature/unpublished",
+        tip,
+        true,
+        "test remote ref",
+    )
+    .unwrap();
+    repo.find_branch(branch_name, BranchType::Local)
+        .unwrap()
+        .set_upstream(Some("origin/feature/unpublished"))
+        .unwrap();
+
+    let pushed = unpublished_commits(&repo_path, branch_name, base.as_deref()).unwrap();
+    assert!(pushed.commits.is_empty());
+    assert_eq!(
+        pushed.upstream.as_deref(),
+        Some("origin/feature/unpublished")
+    );
+}
+
+#[test]
+fn branch_retirement_detects_merged_and_deleted_branches() {
+    let repo_path = empty_dir("retired-src");
+    let Some(repo) = skip_if_xdev(|| init_repo_with_commit(&repo_path)) else {
+        return;
+    };
+    let base_name = repo.head().unwrap().shorthand().unwrap().to_string();
+    let base = format!("refs/heads/{}", base_name);
+    let tip = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature/fresh", &tip, false).unwrap();
+    repo.branch("feature/gone", &tip, false).unwrap();
+
+    // A branch at the base tip has no work of its own, so nothing is lost: merged.
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/fresh", Some(&base)).unwrap(),
+        Some(BranchRetirement::Merged(base_name.clone()))
+    );
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/missing", Some(&base)).unwrap(),
+        Some(BranchRetirement::Deleted)
+    );
+
+    // A branch with a commit of its own that the base then moves past: merged.
+    let merged_root = empty_dir("retired-merged");
+    let merged_path = merged_root.join("dst");
+    create_worktree(
+        &repo_path,
+        &merged_path,
+        "feature/merged",
+        None,
+        &RemoteOptions::default(),
+    )
+    .unwrap();
+    let merged_repo = Repository::open(&merged_path).unwrap();
+    let work = add_commit(&merged_repo, "work.txt", "work").unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/merged", Some(&base)).unwrap(),
+        None
+    );
+    // Fast-forwarded into the base: merged even before the base moves on (as for destroy).
+    repo.reference(&base, work, true, "merge").unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/merged", Some(&base)).unwrap(),
+        Some(BranchRetirement::Merged(base_name.clone()))
+    );
+    assert!(is_merged(&repo_path, "refs/heads/feature/merged", &base).unwrap());
+    add_commit(&repo, "later.txt", "later").unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/merged", Some(&base)).unwrap(),
+        Some(BranchRetirement::Merged(base_name.clone()))
+    );
+    // The untouched branch is behind the base now; gc and destroy agree it is merged.
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/fresh", Some(&base)).unwrap(),
+        Some(BranchRetirement::Merged(base_name.clone()))
+    );
+    assert!(is_merged(&repo_path, "refs/heads/feature/fresh", &base).unwrap());
+
+    // A branch with commits of its own whose upstream was pruned.
+    let worktree_root = empty_dir("retired-root");
+    let worktree_path = worktree_root.join("dst");
+    create_worktree(
+        &repo_path,
+        &worktree_path,
+        "feature/gone",
+        None,
+        &RemoteOptions::default(),
+    )
+    .unwrap();
+    let worktree_repo = Repository::open(&worktree_path).unwrap();
+    let own = add_commit(&worktree_repo, "own.txt", "own").unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/gone", Some(&base)).unwrap(),
+        None
+    );
+    repo.remote("origin", repo_path.to_str().unwrap()).unwrap();
+    let mut tracking = repo
+        .reference(
+            "refs/remotes/origin/feature/gone",
+            own,
+            true,
+            "test remote ref",
+        )
+        .unwrap();
+    repo.find_branch("feature/gone", BranchType::Local)
+        .unwrap()
+        .set_upstream(Some("origin/feature/gone"))
+        .unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/gone", Some(&base)).unwrap(),
+        None
+    );
+    tracking.delete().unwrap();
+    // The deleted remote branch was the only published copy of `own`.
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/gone", Some(&base)).unwrap(),
+        Some(BranchRetirement::RemoteDeleted {
+            upstream: "origin/feature/gone".into(),
+            unpushed: 1,
+        })
+    );
+    repo.reference("refs/remotes/origin/release", own, true, "test remote ref")
+        .unwrap();
+    assert_eq!(
+        branch_retirement(&repo_path, "feature/gone", Some(&base)).unwrap(),
+        Some(BranchRetirement::RemoteDeleted {
+            upstream: "origin/feature/gone".into(),
+            unpushed: 0,
+        })
+    );
+}
+
+#[test]
+fn fetch_pruned_removes_deleted_remote_branches() {
+    let origin_path = empty_dir("origin-prune");
+    let Some(origin_repo) = skip_if_xdev(|| init_repo_with_commit(&origin_path)) else {
+        return;
+    };
+    let tip = origin_repo.head().unwrap().peel_to_commit().unwrap();
+    origin_repo.branch("topic", &tip, false).unwrap();
+
+    let clone_path = empty_dir("clone-prune");
+    let Some(_) = skip_if_xdev(|| clone_repo(origin_path.to_str().unwrap(), &clone_path, "origin"))
+    else {
+        return;
+    };
+    let clone = Repository::open(&clone_path).unwrap();
+    assert!(clone.find_reference("refs/remotes/origin/topic").is_ok());
+
+    origin_repo
+        .find_branch("topic", BranchType::Local)
+        .unwrap()
+        .delete()
+        .unwrap();
+    fetch_and_fast_forward(&clone_path, &RemoteOptions::default()).unwrap();
+    assert!(clone.find_reference("refs/remotes/origin/topic").is_ok());
+    fetch_pruned(&clone_path, &RemoteOptions::default()).unwrap();
+    assert!(clone.find_reference("refs/remotes/origin/topic").is_err());
+}